@@ -0,0 +1,1372 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::selector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnpackError {
+    /// 数据长度不足
+    DataTooShort,
+    /// 数据格式错误或存在多余字节
+    InvalidData,
+    /// 未知的选择器
+    UnknownSelector,
+}
+
+/// 按小端序顺序读取指令参数
+pub struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], UnpackError> {
+        if self.data.len() < N {
+            return Err(UnpackError::DataTooShort);
+        }
+        let (bytes, rest) = self.data.split_at(N);
+        self.data = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, UnpackError> {
+        Ok(self.read_bytes::<1>()?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, UnpackError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(UnpackError::InvalidData),
+        }
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, UnpackError> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, UnpackError> {
+        Ok(u64::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, UnpackError> {
+        Ok(i64::from_le_bytes(self.read_bytes()?))
+    }
+
+    pub fn read_u128(&mut self) -> Result<u128, UnpackError> {
+        Ok(u128::from_le_bytes(self.read_bytes()?))
+    }
+
+    /// Borsh 字符串：u32 长度 + UTF-8 字节
+    pub fn read_string(&mut self) -> Result<String, UnpackError> {
+        let len = self.read_u32()? as usize;
+        if self.data.len() < len {
+            return Err(UnpackError::DataTooShort);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        let s = core::str::from_utf8(bytes).map_err(|_| UnpackError::InvalidData)?;
+        Ok(String::from(s))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// 参数必须恰好读完
+    pub fn finish(self) -> Result<(), UnpackError> {
+        match self.data.is_empty() {
+            true => Ok(()),
+            false => Err(UnpackError::InvalidData),
+        }
+    }
+}
+
+/// 单条指令的参数编解码（不含选择器）
+pub trait Args: Sized {
+    fn pack_into(&self, out: &mut Vec<u8>);
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError>;
+}
+
+/// 交易截止条件：标志字节 + 按标志出现的过期槽位 / unix 时间戳
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Deadline {
+    /// 当前槽位超过该值时失败
+    pub slot: Option<u64>,
+    /// 当前 unix 时间戳超过该值时失败
+    pub unix_timestamp: Option<i64>,
+}
+
+impl Deadline {
+    pub const NONE: Self = Self {
+        slot: None,
+        unix_timestamp: None,
+    };
+
+    const FLAG_SLOT: u8 = 1 << 0;
+    const FLAG_UNIX_TIMESTAMP: u8 = 1 << 1;
+
+    pub fn pack_into(&self, out: &mut Vec<u8>) {
+        let mut flags = 0;
+        if self.slot.is_some() {
+            flags |= Self::FLAG_SLOT;
+        }
+        if self.unix_timestamp.is_some() {
+            flags |= Self::FLAG_UNIX_TIMESTAMP;
+        }
+        out.push(flags);
+        if let Some(slot) = self.slot {
+            out.extend_from_slice(&slot.to_le_bytes());
+        }
+        if let Some(unix_timestamp) = self.unix_timestamp {
+            out.extend_from_slice(&unix_timestamp.to_le_bytes());
+        }
+    }
+
+    pub fn unpack(reader: &mut Reader) -> Result<Self, UnpackError> {
+        let flags = reader.read_u8()?;
+        if flags & !(Self::FLAG_SLOT | Self::FLAG_UNIX_TIMESTAMP) != 0 {
+            return Err(UnpackError::InvalidData);
+        }
+        Ok(Self {
+            slot: match flags & Self::FLAG_SLOT {
+                0 => None,
+                _ => Some(reader.read_u64()?),
+            },
+            unix_timestamp: match flags & Self::FLAG_UNIX_TIMESTAMP {
+                0 => None,
+                _ => Some(reader.read_i64()?),
+            },
+        })
+    }
+}
+
+/// 代理合约在 CPI 前后自行校验的交换约束，附加在各交换指令参数之后。
+/// 字段按顺序编码，末尾取默认值的字段可省略，全部默认时整体省略。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapGuard {
+    /// 目标账户实际增加的最小数量，0 表示不检查
+    pub min_out: u64,
+    /// 来源账户实际减少的最大数量，`u64::MAX` 表示不检查
+    pub max_in: u64,
+    /// CPI 前检查的截止条件，取代单独的过期槽位指令
+    pub deadline: Deadline,
+}
+
+impl Default for SwapGuard {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl SwapGuard {
+    pub const NONE: Self = Self {
+        min_out: 0,
+        max_in: u64::MAX,
+        deadline: Deadline::NONE,
+    };
+
+    pub fn pack_into(&self, out: &mut Vec<u8>) {
+        let has_deadline = self.deadline != Self::NONE.deadline;
+        let has_max_in = has_deadline || self.max_in != Self::NONE.max_in;
+        let has_min_out = has_max_in || self.min_out != Self::NONE.min_out;
+
+        if has_min_out {
+            out.extend_from_slice(&self.min_out.to_le_bytes());
+        }
+        if has_max_in {
+            out.extend_from_slice(&self.max_in.to_le_bytes());
+        }
+        if has_deadline {
+            self.deadline.pack_into(out);
+        }
+    }
+
+    pub fn unpack(reader: &mut Reader) -> Result<Self, UnpackError> {
+        let mut guard = Self::NONE;
+        if reader.is_empty() {
+            return Ok(guard);
+        }
+        guard.min_out = reader.read_u64()?;
+        if reader.is_empty() {
+            return Ok(guard);
+        }
+        guard.max_in = reader.read_u64()?;
+        if reader.is_empty() {
+            return Ok(guard);
+        }
+        guard.deadline = Deadline::unpack(reader)?;
+        Ok(guard)
+    }
+}
+
+/// Pump 内盘买入：买入 `token_amount` 个代币，最多花费 `max_sol_cost` lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpBuyArgs {
+    pub token_amount: u64,
+    pub max_sol_cost: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpBuyArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.token_amount.to_le_bytes());
+        out.extend_from_slice(&self.max_sol_cost.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            token_amount: reader.read_u64()?,
+            max_sol_cost: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Pump 内盘按 SOL 金额买入：花费 `sol_amount` lamports（含手续费），代币数量由代理合约
+/// 按执行时的联合曲线储备计算，少于 `min_token_amount` 时拒绝
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpBuyExactSolArgs {
+    pub sol_amount: u64,
+    pub min_token_amount: u64,
+    /// 从 `sol_amount` 中预留、不参与代币数量计算的 lamports；`max_sol_cost` 仍为 `sol_amount`。
+    ///
+    /// 代理合约按 Global 账户的费率计算代币数量，而 Pump 实际按 fee_config 的费率收费。两者不同时
+    /// 调用方需按 `sol_amount * (fee_config 费率 - Global 费率) / 10_000` 另加几 lamports 的取整余量
+    /// 设置该值，否则买入花费会超过 `sol_amount` 而被 Pump 拒绝；小于 3 时按 3 处理
+    pub sol_margin: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpBuyExactSolArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.sol_amount.to_le_bytes());
+        out.extend_from_slice(&self.min_token_amount.to_le_bytes());
+        out.extend_from_slice(&self.sol_margin.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            sol_amount: reader.read_u64()?,
+            min_token_amount: reader.read_u64()?,
+            sol_margin: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Pump 统一交易：联合曲线未完成时走内盘，已迁移时改走该 mint 的 PumpAMM 标准池。
+/// 买入时 `sol_limit` 为最多花费的 SOL，卖出时为最少收到的 SOL
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpTradeArgs {
+    pub is_buy: bool,
+    pub token_amount: u64,
+    pub sol_limit: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpTradeArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.push(self.is_buy as u8);
+        out.extend_from_slice(&self.token_amount.to_le_bytes());
+        out.extend_from_slice(&self.sol_limit.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            is_buy: reader.read_bool()?,
+            token_amount: reader.read_u64()?,
+            sol_limit: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Pump 内盘卖出：卖出 `token_amount` 个代币，至少收到 `min_sol_output` lamports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpSellArgs {
+    pub token_amount: u64,
+    pub min_sol_output: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpSellArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.token_amount.to_le_bytes());
+        out.extend_from_slice(&self.min_sol_output.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            token_amount: reader.read_u64()?,
+            min_sol_output: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// PumpAMM 外盘买入：买入 `base_amount_out` 个代币，最多花费 `max_quote_amount_in`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpAmmBuyArgs {
+    pub base_amount_out: u64,
+    pub max_quote_amount_in: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpAmmBuyArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.base_amount_out.to_le_bytes());
+        out.extend_from_slice(&self.max_quote_amount_in.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            base_amount_out: reader.read_u64()?,
+            max_quote_amount_in: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// PumpAMM 外盘按报价币金额买入：花费 `spendable_quote_in`（WSOL），至少收到 `min_base_amount_out`，
+/// `track_volume` 为真时计入用户交易量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpAmmBuyExactQuoteInArgs {
+    pub spendable_quote_in: u64,
+    pub min_base_amount_out: u64,
+    pub track_volume: bool,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpAmmBuyExactQuoteInArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.spendable_quote_in.to_le_bytes());
+        out.extend_from_slice(&self.min_base_amount_out.to_le_bytes());
+        out.push(self.track_volume as u8);
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            spendable_quote_in: reader.read_u64()?,
+            min_base_amount_out: reader.read_u64()?,
+            track_volume: reader.read_bool()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// PumpAMM 添加流动性：铸造 `lp_token_amount_out` 个 LP 代币，两侧投入分别不超过
+/// `max_base_amount_in` / `max_quote_amount_in`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpAmmDepositArgs {
+    pub lp_token_amount_out: u64,
+    pub max_base_amount_in: u64,
+    pub max_quote_amount_in: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpAmmDepositArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.lp_token_amount_out.to_le_bytes());
+        out.extend_from_slice(&self.max_base_amount_in.to_le_bytes());
+        out.extend_from_slice(&self.max_quote_amount_in.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            lp_token_amount_out: reader.read_u64()?,
+            max_base_amount_in: reader.read_u64()?,
+            max_quote_amount_in: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// PumpAMM 移除流动性：销毁 `lp_token_amount_in` 个 LP 代币，两侧至少分别收到
+/// `min_base_amount_out` / `min_quote_amount_out`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpAmmWithdrawArgs {
+    pub lp_token_amount_in: u64,
+    pub min_base_amount_out: u64,
+    pub min_quote_amount_out: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpAmmWithdrawArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.lp_token_amount_in.to_le_bytes());
+        out.extend_from_slice(&self.min_base_amount_out.to_le_bytes());
+        out.extend_from_slice(&self.min_quote_amount_out.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            lp_token_amount_in: reader.read_u64()?,
+            min_base_amount_out: reader.read_u64()?,
+            min_quote_amount_out: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// PumpAMM 外盘卖出：卖出 `base_amount_in` 个代币，至少收到 `min_quote_amount_out`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PumpAmmSellArgs {
+    pub base_amount_in: u64,
+    pub min_quote_amount_out: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PumpAmmSellArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.base_amount_in.to_le_bytes());
+        out.extend_from_slice(&self.min_quote_amount_out.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            base_amount_in: reader.read_u64()?,
+            min_quote_amount_out: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 创建关联代币账户，`idempotent` 为真时账户已存在也不报错
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreateAtaArgs {
+    pub idempotent: bool,
+}
+
+impl Args for CreateAtaArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.push(self.idempotent as u8);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        // 空数据与 ATA 程序的旧版 Create 一致
+        if data.is_empty() {
+            return Ok(Self { idempotent: false });
+        }
+        let mut reader = Reader::new(data);
+        let args = Self {
+            idempotent: reader.read_bool()?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 当前槽位超过 `expiry_slot` 时交易失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpiredSlotArgs {
+    pub expiry_slot: u64,
+}
+
+impl Args for ExpiredSlotArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.expiry_slot.to_le_bytes());
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            expiry_slot: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 有效期窗口：槽位与 unix 时间戳的上下界，默认值表示该边界不检查
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidityWindowArgs {
+    /// 当前槽位小于该值时失败（定时开盘）
+    pub min_slot: u64,
+    /// 当前槽位大于该值时失败
+    pub max_slot: u64,
+    /// 当前 unix 时间戳小于该值时失败
+    pub min_unix_timestamp: i64,
+    /// 当前 unix 时间戳大于该值时失败
+    pub max_unix_timestamp: i64,
+}
+
+impl Default for ValidityWindowArgs {
+    fn default() -> Self {
+        Self {
+            min_slot: 0,
+            max_slot: u64::MAX,
+            min_unix_timestamp: i64::MIN,
+            max_unix_timestamp: i64::MAX,
+        }
+    }
+}
+
+impl Args for ValidityWindowArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.min_slot.to_le_bytes());
+        out.extend_from_slice(&self.max_slot.to_le_bytes());
+        out.extend_from_slice(&self.min_unix_timestamp.to_le_bytes());
+        out.extend_from_slice(&self.max_unix_timestamp.to_le_bytes());
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            min_slot: reader.read_u64()?,
+            max_slot: reader.read_u64()?,
+            min_unix_timestamp: reader.read_i64()?,
+            max_unix_timestamp: reader.read_i64()?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Raydium AMM v4 交换参数（旧版选择器），`instruction` 为 Raydium 指令标签，
+/// 只接受 swap_base_in / swap_base_out，新客户端应使用 [`RaydiumSwapBaseInArgs`] / [`RaydiumSwapBaseOutArgs`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaydiumSwapArgs {
+    pub instruction: u8,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for RaydiumSwapArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.push(self.instruction);
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.other_amount_threshold.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            instruction: match reader.read_u8()? {
+                tag @ (selector::raydium_amm::SWAP_BASE_IN
+                | selector::raydium_amm::SWAP_BASE_OUT) => tag,
+                _ => return Err(UnpackError::InvalidData),
+            },
+            amount: reader.read_u64()?,
+            other_amount_threshold: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 通用精确输入：卖出 `amount_in`，至少收到 `minimum_amount_out`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapExactInArgs {
+    pub amount_in: u64,
+    pub minimum_amount_out: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for SwapExactInArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.amount_in.to_le_bytes());
+        out.extend_from_slice(&self.minimum_amount_out.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            amount_in: reader.read_u64()?,
+            minimum_amount_out: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 通用精确输出：收到 `amount_out`，最多花费 `max_amount_in`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapExactOutArgs {
+    pub max_amount_in: u64,
+    pub amount_out: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for SwapExactOutArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.max_amount_in.to_le_bytes());
+        out.extend_from_slice(&self.amount_out.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            max_amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Raydium AMM v4 / CPMM 精确输入，与 [`SwapExactInArgs`] 相同
+pub type RaydiumSwapBaseInArgs = SwapExactInArgs;
+
+/// Raydium AMM v4 / CPMM 精确输出，与 [`SwapExactOutArgs`] 相同
+pub type RaydiumSwapBaseOutArgs = SwapExactOutArgs;
+
+/// Raydium CLMM swap_v2：`is_base_input` 为真时 `amount` 为精确输入、`other_amount_threshold` 为最小输出，
+/// 否则 `amount` 为精确输出、`other_amount_threshold` 为最大输入；`sqrt_price_limit_x64` 为 0 表示不限价
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaydiumClmmSwapArgs {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub is_base_input: bool,
+    pub guard: SwapGuard,
+}
+
+impl Args for RaydiumClmmSwapArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.other_amount_threshold.to_le_bytes());
+        out.extend_from_slice(&self.sqrt_price_limit_x64.to_le_bytes());
+        out.push(self.is_base_input as u8);
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            amount: reader.read_u64()?,
+            other_amount_threshold: reader.read_u64()?,
+            sqrt_price_limit_x64: reader.read_u128()?,
+            is_base_input: reader.read_bool()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Orca Whirlpool swap / swap_v2：`amount_specified_is_input` 决定 `amount` 是精确输入还是精确输出，
+/// `a_to_b` 为真时用 token A 换 token B
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrcaSwapArgs {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub sqrt_price_limit: u128,
+    pub amount_specified_is_input: bool,
+    pub a_to_b: bool,
+    pub guard: SwapGuard,
+}
+
+impl Args for OrcaSwapArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.other_amount_threshold.to_le_bytes());
+        out.extend_from_slice(&self.sqrt_price_limit.to_le_bytes());
+        out.push(self.amount_specified_is_input as u8);
+        out.push(self.a_to_b as u8);
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            amount: reader.read_u64()?,
+            other_amount_threshold: reader.read_u64()?,
+            sqrt_price_limit: reader.read_u128()?,
+            amount_specified_is_input: reader.read_bool()?,
+            a_to_b: reader.read_bool()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Raydium LaunchLab 买卖参数，字段顺序与 LaunchLab 指令一致：
+/// 精确输入时为 (amount_in, minimum_amount_out)，精确输出时为 (amount_out, maximum_amount_in)，
+/// `share_fee_rate` 为分享者费率，不使用时传 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaunchLabArgs {
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub share_fee_rate: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for LaunchLabArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.amount.to_le_bytes());
+        out.extend_from_slice(&self.other_amount_threshold.to_le_bytes());
+        out.extend_from_slice(&self.share_fee_rate.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            amount: reader.read_u64()?,
+            other_amount_threshold: reader.read_u64()?,
+            share_fee_rate: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// OpenBook v2 吃单参数，订单类型固定为 IOC；目标代币账户实际到账不足 `min_amount_out` 时整笔失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenBookTakeArgs {
+    pub is_bid: bool,
+    pub price_lots: i64,
+    pub max_base_lots: i64,
+    pub max_quote_lots_including_fees: i64,
+    /// 单次最多撮合的挂单数量
+    pub limit: u8,
+    /// 最少成交量：目标代币账户（买单为 base、卖单为 quote）的最少到账数量，0 按 1 处理
+    pub min_amount_out: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for OpenBookTakeArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.push(self.is_bid as u8);
+        out.extend_from_slice(&self.price_lots.to_le_bytes());
+        out.extend_from_slice(&self.max_base_lots.to_le_bytes());
+        out.extend_from_slice(&self.max_quote_lots_including_fees.to_le_bytes());
+        out.push(self.limit);
+        out.extend_from_slice(&self.min_amount_out.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            is_bid: reader.read_bool()?,
+            price_lots: reader.read_i64()?,
+            max_base_lots: reader.read_i64()?,
+            max_quote_lots_including_fees: reader.read_i64()?,
+            limit: reader.read_u8()?,
+            min_amount_out: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Phoenix 吃单参数，以 ImmediateOrCancel 订单包下单，`price_in_ticks` 为限价，
+/// 成交不足 `min_base_lots_to_fill` / `min_quote_lots_to_fill` 时整笔失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhoenixTakeArgs {
+    pub is_bid: bool,
+    pub price_in_ticks: u64,
+    pub num_base_lots: u64,
+    pub num_quote_lots: u64,
+    pub min_base_lots_to_fill: u64,
+    pub min_quote_lots_to_fill: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for PhoenixTakeArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.push(self.is_bid as u8);
+        out.extend_from_slice(&self.price_in_ticks.to_le_bytes());
+        out.extend_from_slice(&self.num_base_lots.to_le_bytes());
+        out.extend_from_slice(&self.num_quote_lots.to_le_bytes());
+        out.extend_from_slice(&self.min_base_lots_to_fill.to_le_bytes());
+        out.extend_from_slice(&self.min_quote_lots_to_fill.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            is_bid: reader.read_bool()?,
+            price_in_ticks: reader.read_u64()?,
+            num_base_lots: reader.read_u64()?,
+            num_quote_lots: reader.read_u64()?,
+            min_base_lots_to_fill: reader.read_u64()?,
+            min_quote_lots_to_fill: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Moonshot 买卖参数，对应 Moonshot 的 `TradeParams`：`fixed_side` 决定 `token_amount`
+/// 与 `collateral_amount`（SOL）哪一侧固定，另一侧由 Moonshot 按 `slippage_bps` 放宽
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoonshotTradeArgs {
+    pub token_amount: u64,
+    pub collateral_amount: u64,
+    pub fixed_side: u8,
+    pub slippage_bps: u64,
+    pub guard: SwapGuard,
+}
+
+impl Args for MoonshotTradeArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.token_amount.to_le_bytes());
+        out.extend_from_slice(&self.collateral_amount.to_le_bytes());
+        out.push(self.fixed_side);
+        out.extend_from_slice(&self.slippage_bps.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            token_amount: reader.read_u64()?,
+            collateral_amount: reader.read_u64()?,
+            fixed_side: match reader.read_u8()? {
+                side @ (selector::moonshot::FIXED_SIDE_EXACT_IN
+                | selector::moonshot::FIXED_SIDE_EXACT_OUT) => side,
+                _ => return Err(UnpackError::InvalidData),
+            },
+            slippage_bps: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// Pump 发币并首笔买入：先以 `name` / `symbol` / `uri` / `creator` 调用 Pump 的 `create`，
+/// 再买入 `token_amount` 个代币，最多花费 `max_sol_cost` lamports。
+///
+/// `guard.max_in` 按用户钱包 lamports 的实际减少量计算，除买入花费外还包含买入时可能
+/// 初始化的 `user_volume_accumulator` 的租金，需要为其预留余量
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PumpLaunchArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: [u8; 32],
+    pub token_amount: u64,
+    pub max_sol_cost: u64,
+    pub guard: SwapGuard,
+}
+
+/// 按 Borsh 格式写入字符串
+pub fn pack_string(s: &str, out: &mut Vec<u8>) {
+    out.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    out.extend_from_slice(s.as_bytes());
+}
+
+impl Args for PumpLaunchArgs {
+    fn pack_into(&self, out: &mut Vec<u8>) {
+        pack_string(&self.name, out);
+        pack_string(&self.symbol, out);
+        pack_string(&self.uri, out);
+        out.extend_from_slice(&self.creator);
+        out.extend_from_slice(&self.token_amount.to_le_bytes());
+        out.extend_from_slice(&self.max_sol_cost.to_le_bytes());
+        self.guard.pack_into(out);
+    }
+
+    fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        let mut reader = Reader::new(data);
+        let args = Self {
+            name: reader.read_string()?,
+            symbol: reader.read_string()?,
+            uri: reader.read_string()?,
+            creator: reader.read_bytes()?,
+            token_amount: reader.read_u64()?,
+            max_sol_cost: reader.read_u64()?,
+            guard: SwapGuard::unpack(&mut reader)?,
+        };
+        reader.finish()?;
+        Ok(args)
+    }
+}
+
+/// 代理合约支持的全部指令
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyInstruction {
+    PumpBuy(PumpBuyArgs),
+    PumpSell(PumpSellArgs),
+    PumpAmmBuy(PumpAmmBuyArgs),
+    PumpAmmSell(PumpAmmSellArgs),
+    CreateAta(CreateAtaArgs),
+    ExpiredSlot(ExpiredSlotArgs),
+    ValidityWindow(ValidityWindowArgs),
+    RaydiumBuy(RaydiumSwapArgs),
+    RaydiumSell(RaydiumSwapArgs),
+    RaydiumSwapBaseIn(RaydiumSwapBaseInArgs),
+    RaydiumSwapBaseOut(RaydiumSwapBaseOutArgs),
+    /// 与 `RaydiumSwapBaseIn` 参数相同，但传入真实的 OpenBook 市场账户
+    RaydiumMarketSwapBaseIn(RaydiumSwapBaseInArgs),
+    RaydiumMarketSwapBaseOut(RaydiumSwapBaseOutArgs),
+    RaydiumCpmmSwapBaseInput(RaydiumSwapBaseInArgs),
+    RaydiumCpmmSwapBaseOutput(RaydiumSwapBaseOutArgs),
+    RaydiumClmmSwap(RaydiumClmmSwapArgs),
+    OrcaSwap(OrcaSwapArgs),
+    OrcaSwapV2(OrcaSwapArgs),
+    MeteoraDlmmSwap(SwapExactInArgs),
+    MeteoraDlmmSwapExactOut(SwapExactOutArgs),
+    MeteoraDammSwap(SwapExactInArgs),
+    MeteoraDammV2Swap(SwapExactInArgs),
+    LaunchLabBuyExactIn(LaunchLabArgs),
+    LaunchLabBuyExactOut(LaunchLabArgs),
+    LaunchLabSellExactIn(LaunchLabArgs),
+    LaunchLabSellExactOut(LaunchLabArgs),
+    OpenBookV2Take(OpenBookTakeArgs),
+    PhoenixTake(PhoenixTakeArgs),
+    MoonshotBuy(MoonshotTradeArgs),
+    MoonshotSell(MoonshotTradeArgs),
+    PumpLaunch(PumpLaunchArgs),
+    TokenSwap(SwapExactInArgs),
+    PumpBuyExactSol(PumpBuyExactSolArgs),
+    PumpTrade(PumpTradeArgs),
+    PumpAmmBuyExactQuoteIn(PumpAmmBuyExactQuoteInArgs),
+    PumpAmmDeposit(PumpAmmDepositArgs),
+    PumpAmmWithdraw(PumpAmmWithdrawArgs),
+}
+
+impl ProxyInstruction {
+    pub fn selector(&self) -> &'static [u8; 8] {
+        match self {
+            Self::PumpBuy(_) => &selector::PUMP_BUY,
+            Self::PumpSell(_) => &selector::PUMP_SELL,
+            Self::PumpAmmBuy(_) => &selector::PUMP_AMM_BUY,
+            Self::PumpAmmSell(_) => &selector::PUMP_AMM_SELL,
+            Self::CreateAta(_) => &selector::CREATE_ATA,
+            Self::ExpiredSlot(_) => &selector::EXPIRED_SLOT,
+            Self::ValidityWindow(_) => &selector::VALIDITY_WINDOW,
+            Self::RaydiumBuy(_) => &selector::RAYDIUM_BUY,
+            Self::RaydiumSell(_) => &selector::RAYDIUM_SELL,
+            Self::RaydiumSwapBaseIn(_) => &selector::RAYDIUM_SWAP_BASE_IN,
+            Self::RaydiumSwapBaseOut(_) => &selector::RAYDIUM_SWAP_BASE_OUT,
+            Self::RaydiumMarketSwapBaseIn(_) => &selector::RAYDIUM_MARKET_SWAP_BASE_IN,
+            Self::RaydiumMarketSwapBaseOut(_) => &selector::RAYDIUM_MARKET_SWAP_BASE_OUT,
+            Self::RaydiumCpmmSwapBaseInput(_) => &selector::RAYDIUM_CPMM_SWAP_BASE_INPUT,
+            Self::RaydiumCpmmSwapBaseOutput(_) => &selector::RAYDIUM_CPMM_SWAP_BASE_OUTPUT,
+            Self::RaydiumClmmSwap(_) => &selector::RAYDIUM_CLMM_SWAP,
+            Self::OrcaSwap(_) => &selector::ORCA_SWAP,
+            Self::OrcaSwapV2(_) => &selector::ORCA_SWAP_V2,
+            Self::MeteoraDlmmSwap(_) => &selector::METEORA_DLMM_SWAP,
+            Self::MeteoraDlmmSwapExactOut(_) => &selector::METEORA_DLMM_SWAP_EXACT_OUT,
+            Self::MeteoraDammSwap(_) => &selector::METEORA_DAMM_SWAP,
+            Self::MeteoraDammV2Swap(_) => &selector::METEORA_DAMM_V2_SWAP,
+            Self::LaunchLabBuyExactIn(_) => &selector::LAUNCHLAB_BUY_EXACT_IN,
+            Self::LaunchLabBuyExactOut(_) => &selector::LAUNCHLAB_BUY_EXACT_OUT,
+            Self::LaunchLabSellExactIn(_) => &selector::LAUNCHLAB_SELL_EXACT_IN,
+            Self::LaunchLabSellExactOut(_) => &selector::LAUNCHLAB_SELL_EXACT_OUT,
+            Self::OpenBookV2Take(_) => &selector::OPENBOOK_V2_TAKE,
+            Self::PhoenixTake(_) => &selector::PHOENIX_TAKE,
+            Self::MoonshotBuy(_) => &selector::MOONSHOT_BUY,
+            Self::MoonshotSell(_) => &selector::MOONSHOT_SELL,
+            Self::PumpLaunch(_) => &selector::PUMP_LAUNCH,
+            Self::TokenSwap(_) => &selector::TOKEN_SWAP,
+            Self::PumpBuyExactSol(_) => &selector::PUMP_BUY_EXACT_SOL,
+            Self::PumpTrade(_) => &selector::PUMP_TRADE,
+            Self::PumpAmmBuyExactQuoteIn(_) => &selector::PUMP_AMM_BUY_EXACT_QUOTE_IN,
+            Self::PumpAmmDeposit(_) => &selector::PUMP_AMM_DEPOSIT,
+            Self::PumpAmmWithdraw(_) => &selector::PUMP_AMM_WITHDRAW,
+        }
+    }
+
+    /// 选择器 + 参数
+    pub fn pack(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(64);
+        out.extend_from_slice(self.selector());
+        match self {
+            Self::PumpBuy(args) => args.pack_into(&mut out),
+            Self::PumpSell(args) => args.pack_into(&mut out),
+            Self::PumpAmmBuy(args) => args.pack_into(&mut out),
+            Self::PumpAmmSell(args) => args.pack_into(&mut out),
+            Self::CreateAta(args) => args.pack_into(&mut out),
+            Self::ExpiredSlot(args) => args.pack_into(&mut out),
+            Self::ValidityWindow(args) => args.pack_into(&mut out),
+            Self::RaydiumBuy(args) => args.pack_into(&mut out),
+            Self::RaydiumSell(args) => args.pack_into(&mut out),
+            Self::RaydiumSwapBaseIn(args) => args.pack_into(&mut out),
+            Self::RaydiumSwapBaseOut(args) => args.pack_into(&mut out),
+            Self::RaydiumMarketSwapBaseIn(args) => args.pack_into(&mut out),
+            Self::RaydiumMarketSwapBaseOut(args) => args.pack_into(&mut out),
+            Self::RaydiumCpmmSwapBaseInput(args) => args.pack_into(&mut out),
+            Self::RaydiumCpmmSwapBaseOutput(args) => args.pack_into(&mut out),
+            Self::RaydiumClmmSwap(args) => args.pack_into(&mut out),
+            Self::OrcaSwap(args) => args.pack_into(&mut out),
+            Self::OrcaSwapV2(args) => args.pack_into(&mut out),
+            Self::MeteoraDlmmSwap(args) => args.pack_into(&mut out),
+            Self::MeteoraDlmmSwapExactOut(args) => args.pack_into(&mut out),
+            Self::MeteoraDammSwap(args) => args.pack_into(&mut out),
+            Self::MeteoraDammV2Swap(args) => args.pack_into(&mut out),
+            Self::LaunchLabBuyExactIn(args) => args.pack_into(&mut out),
+            Self::LaunchLabBuyExactOut(args) => args.pack_into(&mut out),
+            Self::LaunchLabSellExactIn(args) => args.pack_into(&mut out),
+            Self::LaunchLabSellExactOut(args) => args.pack_into(&mut out),
+            Self::OpenBookV2Take(args) => args.pack_into(&mut out),
+            Self::PhoenixTake(args) => args.pack_into(&mut out),
+            Self::MoonshotBuy(args) => args.pack_into(&mut out),
+            Self::MoonshotSell(args) => args.pack_into(&mut out),
+            Self::PumpLaunch(args) => args.pack_into(&mut out),
+            Self::TokenSwap(args) => args.pack_into(&mut out),
+            Self::PumpBuyExactSol(args) => args.pack_into(&mut out),
+            Self::PumpTrade(args) => args.pack_into(&mut out),
+            Self::PumpAmmBuyExactQuoteIn(args) => args.pack_into(&mut out),
+            Self::PumpAmmDeposit(args) => args.pack_into(&mut out),
+            Self::PumpAmmWithdraw(args) => args.pack_into(&mut out),
+        }
+        out
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, UnpackError> {
+        if data.len() < 8 {
+            return Err(UnpackError::DataTooShort);
+        }
+        let (method, rest) = data.split_at(8);
+
+        Ok(match method.try_into().unwrap() {
+            selector::PUMP_BUY => Self::PumpBuy(Args::unpack(rest)?),
+            selector::PUMP_SELL => Self::PumpSell(Args::unpack(rest)?),
+            selector::PUMP_AMM_BUY => Self::PumpAmmBuy(Args::unpack(rest)?),
+            selector::PUMP_AMM_SELL => Self::PumpAmmSell(Args::unpack(rest)?),
+            selector::CREATE_ATA => Self::CreateAta(Args::unpack(rest)?),
+            selector::EXPIRED_SLOT => Self::ExpiredSlot(Args::unpack(rest)?),
+            selector::VALIDITY_WINDOW => Self::ValidityWindow(Args::unpack(rest)?),
+            selector::RAYDIUM_BUY => Self::RaydiumBuy(Args::unpack(rest)?),
+            selector::RAYDIUM_SELL => Self::RaydiumSell(Args::unpack(rest)?),
+            selector::RAYDIUM_SWAP_BASE_IN => Self::RaydiumSwapBaseIn(Args::unpack(rest)?),
+            selector::RAYDIUM_SWAP_BASE_OUT => Self::RaydiumSwapBaseOut(Args::unpack(rest)?),
+            selector::RAYDIUM_MARKET_SWAP_BASE_IN => {
+                Self::RaydiumMarketSwapBaseIn(Args::unpack(rest)?)
+            }
+            selector::RAYDIUM_MARKET_SWAP_BASE_OUT => {
+                Self::RaydiumMarketSwapBaseOut(Args::unpack(rest)?)
+            }
+            selector::RAYDIUM_CPMM_SWAP_BASE_INPUT => {
+                Self::RaydiumCpmmSwapBaseInput(Args::unpack(rest)?)
+            }
+            selector::RAYDIUM_CPMM_SWAP_BASE_OUTPUT => {
+                Self::RaydiumCpmmSwapBaseOutput(Args::unpack(rest)?)
+            }
+            selector::RAYDIUM_CLMM_SWAP => Self::RaydiumClmmSwap(Args::unpack(rest)?),
+            selector::ORCA_SWAP => Self::OrcaSwap(Args::unpack(rest)?),
+            selector::ORCA_SWAP_V2 => Self::OrcaSwapV2(Args::unpack(rest)?),
+            selector::METEORA_DLMM_SWAP => Self::MeteoraDlmmSwap(Args::unpack(rest)?),
+            selector::METEORA_DLMM_SWAP_EXACT_OUT => {
+                Self::MeteoraDlmmSwapExactOut(Args::unpack(rest)?)
+            }
+            selector::METEORA_DAMM_SWAP => Self::MeteoraDammSwap(Args::unpack(rest)?),
+            selector::METEORA_DAMM_V2_SWAP => Self::MeteoraDammV2Swap(Args::unpack(rest)?),
+            selector::LAUNCHLAB_BUY_EXACT_IN => Self::LaunchLabBuyExactIn(Args::unpack(rest)?),
+            selector::LAUNCHLAB_BUY_EXACT_OUT => Self::LaunchLabBuyExactOut(Args::unpack(rest)?),
+            selector::LAUNCHLAB_SELL_EXACT_IN => Self::LaunchLabSellExactIn(Args::unpack(rest)?),
+            selector::LAUNCHLAB_SELL_EXACT_OUT => Self::LaunchLabSellExactOut(Args::unpack(rest)?),
+            selector::OPENBOOK_V2_TAKE => Self::OpenBookV2Take(Args::unpack(rest)?),
+            selector::PHOENIX_TAKE => Self::PhoenixTake(Args::unpack(rest)?),
+            selector::MOONSHOT_BUY => Self::MoonshotBuy(Args::unpack(rest)?),
+            selector::MOONSHOT_SELL => Self::MoonshotSell(Args::unpack(rest)?),
+            selector::PUMP_LAUNCH => Self::PumpLaunch(Args::unpack(rest)?),
+            selector::TOKEN_SWAP => Self::TokenSwap(Args::unpack(rest)?),
+            selector::PUMP_BUY_EXACT_SOL => Self::PumpBuyExactSol(Args::unpack(rest)?),
+            selector::PUMP_TRADE => Self::PumpTrade(Args::unpack(rest)?),
+            selector::PUMP_AMM_BUY_EXACT_QUOTE_IN => {
+                Self::PumpAmmBuyExactQuoteIn(Args::unpack(rest)?)
+            }
+            selector::PUMP_AMM_DEPOSIT => Self::PumpAmmDeposit(Args::unpack(rest)?),
+            selector::PUMP_AMM_WITHDRAW => Self::PumpAmmWithdraw(Args::unpack(rest)?),
+            _ => return Err(UnpackError::UnknownSelector),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUARD: SwapGuard = SwapGuard {
+        min_out: 10,
+        max_in: 20,
+        deadline: Deadline {
+            slot: Some(30),
+            unix_timestamp: Some(40),
+        },
+    };
+
+    fn round_trip(instruction: ProxyInstruction) {
+        let data = instruction.pack();
+        assert_eq!(&data[..8], instruction.selector());
+        assert_eq!(ProxyInstruction::unpack(&data), Ok(instruction));
+    }
+
+    #[test]
+    fn proxy_instruction_round_trip() {
+        round_trip(ProxyInstruction::PumpBuy(PumpBuyArgs {
+            token_amount: 1,
+            max_sol_cost: 2,
+            guard: GUARD,
+        }));
+        round_trip(ProxyInstruction::CreateAta(CreateAtaArgs {
+            idempotent: true,
+        }));
+    }
+
+    #[test]
+    fn validity_window_round_trip() {
+        round_trip(ProxyInstruction::ValidityWindow(
+            ValidityWindowArgs::default(),
+        ));
+        round_trip(ProxyInstruction::ValidityWindow(ValidityWindowArgs {
+            min_slot: 1,
+            max_slot: 2,
+            min_unix_timestamp: -3,
+            max_unix_timestamp: 4,
+        }));
+    }
+
+    #[test]
+    fn raydium_swap_round_trip() {
+        round_trip(ProxyInstruction::RaydiumSwapBaseIn(RaydiumSwapBaseInArgs {
+            amount_in: 1,
+            minimum_amount_out: 2,
+            guard: SwapGuard::NONE,
+        }));
+        round_trip(ProxyInstruction::RaydiumSwapBaseIn(RaydiumSwapBaseInArgs {
+            amount_in: u64::MAX,
+            minimum_amount_out: 0,
+            guard: GUARD,
+        }));
+    }
+
+    #[test]
+    fn orca_swap_round_trip() {
+        round_trip(ProxyInstruction::OrcaSwap(OrcaSwapArgs {
+            amount: 1,
+            other_amount_threshold: 2,
+            sqrt_price_limit: u128::MAX,
+            amount_specified_is_input: true,
+            a_to_b: false,
+            guard: GUARD,
+        }));
+    }
+
+    #[test]
+    fn openbook_take_round_trip() {
+        round_trip(ProxyInstruction::OpenBookV2Take(OpenBookTakeArgs {
+            is_bid: true,
+            price_lots: -1,
+            max_base_lots: 2,
+            max_quote_lots_including_fees: 3,
+            limit: 4,
+            min_amount_out: 5,
+            guard: SwapGuard {
+                min_out: 6,
+                ..SwapGuard::NONE
+            },
+        }));
+    }
+
+    #[test]
+    fn moonshot_trade_round_trip() {
+        round_trip(ProxyInstruction::MoonshotBuy(MoonshotTradeArgs {
+            token_amount: 1,
+            collateral_amount: 2,
+            fixed_side: 1,
+            slippage_bps: 3,
+            guard: SwapGuard::NONE,
+        }));
+        round_trip(ProxyInstruction::MoonshotSell(MoonshotTradeArgs {
+            token_amount: 1,
+            collateral_amount: 2,
+            fixed_side: 0,
+            slippage_bps: 3,
+            guard: GUARD,
+        }));
+    }
+
+    #[test]
+    fn pump_launch_round_trip() {
+        round_trip(ProxyInstruction::PumpLaunch(PumpLaunchArgs {
+            name: String::from("name"),
+            symbol: String::from("SYM"),
+            uri: String::new(),
+            creator: [7; 32],
+            token_amount: 1,
+            max_sol_cost: 2,
+            guard: GUARD,
+        }));
+    }
+
+    #[test]
+    fn pump_amm_liquidity_round_trip() {
+        round_trip(ProxyInstruction::PumpAmmDeposit(PumpAmmDepositArgs {
+            lp_token_amount_out: 1,
+            max_base_amount_in: 2,
+            max_quote_amount_in: 3,
+            guard: SwapGuard::NONE,
+        }));
+        round_trip(ProxyInstruction::PumpAmmWithdraw(PumpAmmWithdrawArgs {
+            lp_token_amount_in: 1,
+            min_base_amount_out: 2,
+            min_quote_amount_out: 3,
+            guard: GUARD,
+        }));
+    }
+
+    fn pack_guard(guard: &SwapGuard) -> Vec<u8> {
+        let mut out = Vec::new();
+        guard.pack_into(&mut out);
+        out
+    }
+
+    fn unpack_guard(data: &[u8]) -> Result<SwapGuard, UnpackError> {
+        let mut reader = Reader::new(data);
+        let guard = SwapGuard::unpack(&mut reader)?;
+        reader.finish()?;
+        Ok(guard)
+    }
+
+    #[test]
+    fn swap_guard_omits_trailing_defaults() {
+        let min_out = SwapGuard {
+            min_out: 1,
+            ..SwapGuard::NONE
+        };
+        let max_in = SwapGuard {
+            max_in: 2,
+            ..SwapGuard::NONE
+        };
+        // 只设置截止条件时，前面的 min_out / max_in 仍按默认值编码
+        let deadline = SwapGuard {
+            deadline: Deadline {
+                slot: None,
+                unix_timestamp: Some(3),
+            },
+            ..SwapGuard::NONE
+        };
+
+        for (guard, len) in [
+            (SwapGuard::NONE, 0),
+            (min_out, 8),
+            (max_in, 16),
+            (deadline, 16 + 1 + 8),
+            (GUARD, 16 + 1 + 16),
+        ] {
+            let data = pack_guard(&guard);
+            assert_eq!(data.len(), len);
+            assert_eq!(unpack_guard(&data), Ok(guard));
+        }
+    }
+
+    #[test]
+    fn deadline_rejects_unknown_flags() {
+        let mut data = pack_guard(&SwapGuard {
+            max_in: 1,
+            ..SwapGuard::NONE
+        });
+        data.push(1 << 2);
+        assert_eq!(unpack_guard(&data), Err(UnpackError::InvalidData));
+    }
+
+    #[test]
+    fn finish_rejects_trailing_bytes() {
+        let mut data = ProxyInstruction::PumpBuy(PumpBuyArgs {
+            token_amount: 1,
+            max_sol_cost: 2,
+            guard: GUARD,
+        })
+        .pack();
+        data.push(0);
+        assert_eq!(
+            ProxyInstruction::unpack(&data),
+            Err(UnpackError::InvalidData)
+        );
+
+        let mut data = ProxyInstruction::ExpiredSlot(ExpiredSlotArgs { expiry_slot: 1 }).pack();
+        data.push(0);
+        assert_eq!(
+            ProxyInstruction::unpack(&data),
+            Err(UnpackError::InvalidData)
+        );
+    }
+
+    #[test]
+    fn create_ata_accepts_empty_data() {
+        assert_eq!(
+            CreateAtaArgs::unpack(&[]),
+            Ok(CreateAtaArgs { idempotent: false })
+        );
+        assert_eq!(
+            ProxyInstruction::unpack(&selector::CREATE_ATA),
+            Ok(ProxyInstruction::CreateAta(CreateAtaArgs {
+                idempotent: false
+            }))
+        );
+    }
+
+    #[test]
+    fn unpack_rejects_short_and_unknown_selectors() {
+        assert_eq!(
+            ProxyInstruction::unpack(&[0; 7]),
+            Err(UnpackError::DataTooShort)
+        );
+        assert_eq!(
+            ProxyInstruction::unpack(&[0; 8]),
+            Err(UnpackError::UnknownSelector)
+        );
+    }
+
+    #[test]
+    fn every_selector_is_decoded() {
+        for selector in selector::ALL {
+            let data = [selector.as_slice(), &[0; 8]].concat();
+            assert_ne!(
+                ProxyInstruction::unpack(&data),
+                Err(UnpackError::UnknownSelector),
+                "{selector:?}"
+            );
+        }
+    }
+}
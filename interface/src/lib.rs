@@ -0,0 +1,9 @@
+//! AMM 代理合约的指令接口：选择器与指令编解码，合约与客户端共用。
+#![no_std]
+
+extern crate alloc;
+
+pub mod instruction;
+pub mod selector;
+
+pub use instruction::{ProxyInstruction, UnpackError};
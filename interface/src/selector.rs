@@ -0,0 +1,340 @@
+//! 指令选择器。
+//!
+//! 选择器在编译期由名称计算：`sha256("global:<name>")` 的前 8 字节（与 Anchor 一致），
+//! 下游程序的鉴别器同样由指令名推导，避免手抄字节导致内外盘鉴别器混用。
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H0: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+// 对 `prefix || name` 做 SHA-256，按字节下标直接取值，无需在 const 环境中拼接缓冲区
+const fn sha256(prefix: &[u8], name: &[u8]) -> [u8; 32] {
+    let len = prefix.len() + name.len();
+    let blocks = (len + 9).div_ceil(64);
+    let bit_len = (len as u64) * 8;
+
+    let mut h = H0;
+    let mut block = 0;
+    while block < blocks {
+        let mut w = [0u32; 64];
+        let mut t = 0;
+        while t < 16 {
+            let mut word = 0u32;
+            let mut b = 0;
+            while b < 4 {
+                let i = block * 64 + t * 4 + b;
+                let byte = if i < prefix.len() {
+                    prefix[i]
+                } else if i < len {
+                    name[i - prefix.len()]
+                } else if i == len {
+                    0x80
+                } else if i >= blocks * 64 - 8 {
+                    (bit_len >> ((blocks * 64 - 1 - i) * 8)) as u8
+                } else {
+                    0
+                };
+                word = (word << 8) | byte as u32;
+                b += 1;
+            }
+            w[t] = word;
+            t += 1;
+        }
+        while t < 64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+            t += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        t = 0;
+        while t < 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[t])
+                .wrapping_add(w[t]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+            t += 1;
+        }
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(d);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+        block += 1;
+    }
+
+    let mut out = [0u8; 32];
+    let mut i = 0;
+    while i < 8 {
+        let bytes = h[i].to_be_bytes();
+        out[i * 4] = bytes[0];
+        out[i * 4 + 1] = bytes[1];
+        out[i * 4 + 2] = bytes[2];
+        out[i * 4 + 3] = bytes[3];
+        i += 1;
+    }
+    out
+}
+
+/// `sha256("global:<name>")[..8]`
+pub const fn sighash(name: &str) -> [u8; 8] {
+    let hash = sha256(b"global:", name.as_bytes());
+    [
+        hash[0], hash[1], hash[2], hash[3], hash[4], hash[5], hash[6], hash[7],
+    ]
+}
+
+// 早期版本的卖出选择器取对应买入选择器首字节加一，保留以兼容已部署的客户端
+const fn next_selector(selector: [u8; 8]) -> [u8; 8] {
+    let mut next = selector;
+    next[0] = next[0].wrapping_add(1);
+    next
+}
+
+pub const PUMP_BUY: [u8; 8] = sighash("pump_buy");
+pub const PUMP_SELL: [u8; 8] = next_selector(PUMP_BUY);
+pub const PUMP_AMM_BUY: [u8; 8] = sighash("pump_amm_buy");
+pub const PUMP_AMM_SELL: [u8; 8] = next_selector(PUMP_AMM_BUY);
+pub const CREATE_ATA: [u8; 8] = sighash("ata");
+pub const EXPIRED_SLOT: [u8; 8] = sighash("slot");
+pub const VALIDITY_WINDOW: [u8; 8] = sighash("validity_window");
+// Raydium 买入选择器来源于早期手工分配，无对应名称
+pub const RAYDIUM_BUY: [u8; 8] = [182, 77, 232, 39, 117, 138, 183, 72];
+pub const RAYDIUM_SELL: [u8; 8] = next_selector(RAYDIUM_BUY);
+pub const RAYDIUM_SWAP_BASE_IN: [u8; 8] = sighash("raydium_swap_base_in");
+pub const RAYDIUM_SWAP_BASE_OUT: [u8; 8] = sighash("raydium_swap_base_out");
+pub const RAYDIUM_MARKET_SWAP_BASE_IN: [u8; 8] = sighash("raydium_market_swap_base_in");
+pub const RAYDIUM_MARKET_SWAP_BASE_OUT: [u8; 8] = sighash("raydium_market_swap_base_out");
+pub const RAYDIUM_CPMM_SWAP_BASE_INPUT: [u8; 8] = sighash("raydium_cpmm_swap_base_input");
+pub const RAYDIUM_CPMM_SWAP_BASE_OUTPUT: [u8; 8] = sighash("raydium_cpmm_swap_base_output");
+pub const RAYDIUM_CLMM_SWAP: [u8; 8] = sighash("raydium_clmm_swap");
+pub const ORCA_SWAP: [u8; 8] = sighash("orca_swap");
+pub const ORCA_SWAP_V2: [u8; 8] = sighash("orca_swap_v2");
+pub const METEORA_DLMM_SWAP: [u8; 8] = sighash("meteora_dlmm_swap");
+pub const METEORA_DLMM_SWAP_EXACT_OUT: [u8; 8] = sighash("meteora_dlmm_swap_exact_out");
+pub const METEORA_DAMM_SWAP: [u8; 8] = sighash("meteora_damm_swap");
+pub const METEORA_DAMM_V2_SWAP: [u8; 8] = sighash("meteora_damm_v2_swap");
+pub const LAUNCHLAB_BUY_EXACT_IN: [u8; 8] = sighash("launchlab_buy_exact_in");
+pub const LAUNCHLAB_BUY_EXACT_OUT: [u8; 8] = sighash("launchlab_buy_exact_out");
+pub const LAUNCHLAB_SELL_EXACT_IN: [u8; 8] = sighash("launchlab_sell_exact_in");
+pub const LAUNCHLAB_SELL_EXACT_OUT: [u8; 8] = sighash("launchlab_sell_exact_out");
+pub const OPENBOOK_V2_TAKE: [u8; 8] = sighash("openbook_v2_take");
+pub const PHOENIX_TAKE: [u8; 8] = sighash("phoenix_take");
+pub const MOONSHOT_BUY: [u8; 8] = sighash("moonshot_buy");
+pub const MOONSHOT_SELL: [u8; 8] = sighash("moonshot_sell");
+pub const PUMP_LAUNCH: [u8; 8] = sighash("pump_launch");
+pub const TOKEN_SWAP: [u8; 8] = sighash("token_swap");
+pub const PUMP_BUY_EXACT_SOL: [u8; 8] = sighash("pump_buy_exact_sol");
+pub const PUMP_TRADE: [u8; 8] = sighash("pump_trade");
+pub const PUMP_AMM_BUY_EXACT_QUOTE_IN: [u8; 8] = sighash("pump_amm_buy_exact_quote_in");
+pub const PUMP_AMM_DEPOSIT: [u8; 8] = sighash("pump_amm_deposit");
+pub const PUMP_AMM_WITHDRAW: [u8; 8] = sighash("pump_amm_withdraw");
+
+/// 代理合约的全部选择器，编译期检查互不重复
+pub const ALL: [[u8; 8]; 37] = [
+    PUMP_BUY,
+    PUMP_SELL,
+    PUMP_AMM_BUY,
+    PUMP_AMM_SELL,
+    CREATE_ATA,
+    EXPIRED_SLOT,
+    VALIDITY_WINDOW,
+    RAYDIUM_BUY,
+    RAYDIUM_SELL,
+    RAYDIUM_SWAP_BASE_IN,
+    RAYDIUM_SWAP_BASE_OUT,
+    RAYDIUM_MARKET_SWAP_BASE_IN,
+    RAYDIUM_MARKET_SWAP_BASE_OUT,
+    RAYDIUM_CPMM_SWAP_BASE_INPUT,
+    RAYDIUM_CPMM_SWAP_BASE_OUTPUT,
+    RAYDIUM_CLMM_SWAP,
+    ORCA_SWAP,
+    ORCA_SWAP_V2,
+    METEORA_DLMM_SWAP,
+    METEORA_DLMM_SWAP_EXACT_OUT,
+    METEORA_DAMM_SWAP,
+    METEORA_DAMM_V2_SWAP,
+    LAUNCHLAB_BUY_EXACT_IN,
+    LAUNCHLAB_BUY_EXACT_OUT,
+    LAUNCHLAB_SELL_EXACT_IN,
+    LAUNCHLAB_SELL_EXACT_OUT,
+    OPENBOOK_V2_TAKE,
+    PHOENIX_TAKE,
+    MOONSHOT_BUY,
+    MOONSHOT_SELL,
+    PUMP_LAUNCH,
+    TOKEN_SWAP,
+    PUMP_BUY_EXACT_SOL,
+    PUMP_TRADE,
+    PUMP_AMM_BUY_EXACT_QUOTE_IN,
+    PUMP_AMM_DEPOSIT,
+    PUMP_AMM_WITHDRAW,
+];
+
+/// Pump 内盘程序的指令鉴别器
+pub mod pump {
+    use super::sighash;
+
+    pub const BUY: [u8; 8] = sighash("buy");
+    pub const SELL: [u8; 8] = sighash("sell");
+    pub const CREATE: [u8; 8] = sighash("create");
+}
+
+/// PumpAMM 外盘程序的指令鉴别器
+pub mod pump_amm {
+    use super::sighash;
+
+    pub const BUY: [u8; 8] = sighash("buy");
+    pub const SELL: [u8; 8] = sighash("sell");
+    pub const BUY_EXACT_QUOTE_IN: [u8; 8] = sighash("buy_exact_quote_in");
+    pub const DEPOSIT: [u8; 8] = sighash("deposit");
+    pub const WITHDRAW: [u8; 8] = sighash("withdraw");
+}
+
+/// Raydium AMM v4 的指令标签（非 Anchor 程序，单字节）
+pub mod raydium_amm {
+    pub const SWAP_BASE_IN: u8 = 9;
+    pub const SWAP_BASE_OUT: u8 = 11;
+}
+
+/// Raydium CPMM 程序的指令鉴别器
+pub mod raydium_cpmm {
+    use super::sighash;
+
+    pub const SWAP_BASE_INPUT: [u8; 8] = sighash("swap_base_input");
+    pub const SWAP_BASE_OUTPUT: [u8; 8] = sighash("swap_base_output");
+}
+
+/// Raydium CLMM 程序的指令鉴别器
+pub mod raydium_clmm {
+    use super::sighash;
+
+    pub const SWAP_V2: [u8; 8] = sighash("swap_v2");
+}
+
+/// Orca Whirlpool 程序的指令鉴别器
+pub mod orca {
+    use super::sighash;
+
+    pub const SWAP: [u8; 8] = sighash("swap");
+    pub const SWAP_V2: [u8; 8] = sighash("swap_v2");
+}
+
+/// Meteora DLMM 程序的指令鉴别器
+pub mod meteora_dlmm {
+    use super::sighash;
+
+    pub const SWAP: [u8; 8] = sighash("swap");
+    pub const SWAP_EXACT_OUT: [u8; 8] = sighash("swap_exact_out");
+}
+
+/// Meteora DAMM 程序的指令鉴别器，v1 与 v2 (cp-amm) 的 swap 相同
+pub mod meteora_damm {
+    use super::sighash;
+
+    pub const SWAP: [u8; 8] = sighash("swap");
+}
+
+/// Raydium LaunchLab 程序的指令鉴别器
+pub mod launchlab {
+    use super::sighash;
+
+    pub const BUY_EXACT_IN: [u8; 8] = sighash("buy_exact_in");
+    pub const BUY_EXACT_OUT: [u8; 8] = sighash("buy_exact_out");
+    pub const SELL_EXACT_IN: [u8; 8] = sighash("sell_exact_in");
+    pub const SELL_EXACT_OUT: [u8; 8] = sighash("sell_exact_out");
+}
+
+/// OpenBook v2 程序的指令鉴别器
+pub mod openbook_v2 {
+    use super::sighash;
+
+    pub const PLACE_TAKE_ORDER: [u8; 8] = sighash("place_take_order");
+}
+
+/// Phoenix 程序的指令标签（非 Anchor 程序，单字节）
+pub mod phoenix {
+    pub const SWAP: u8 = 0;
+}
+
+/// Moonshot 程序的指令鉴别器与 `TradeParams.fixed_side` 取值
+pub mod moonshot {
+    use super::sighash;
+
+    pub const BUY: [u8; 8] = sighash("buy");
+    pub const SELL: [u8; 8] = sighash("sell");
+
+    pub const FIXED_SIDE_EXACT_IN: u8 = 0;
+    pub const FIXED_SIDE_EXACT_OUT: u8 = 1;
+}
+
+/// SPL token-swap 及其分叉共用的指令标签
+pub mod token_swap {
+    pub const SWAP: u8 = 1;
+}
+
+const fn eq(a: &[u8; 8], b: &[u8; 8]) -> bool {
+    let mut i = 0;
+    while i < 8 {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const _: () = {
+    let mut i = 0;
+    while i < ALL.len() {
+        let mut j = i + 1;
+        while j < ALL.len() {
+            assert!(!eq(&ALL[i], &ALL[j]), "duplicate proxy selector");
+            j += 1;
+        }
+        i += 1;
+    }
+
+    // 已部署的线上值，改名会直接导致编译失败
+    assert!(eq(&PUMP_BUY, &[82, 225, 119, 231, 78, 29, 45, 70]));
+    assert!(eq(&PUMP_SELL, &[83, 225, 119, 231, 78, 29, 45, 70]));
+    assert!(eq(&PUMP_AMM_BUY, &[129, 59, 179, 195, 110, 135, 61, 2]));
+    assert!(eq(&PUMP_AMM_SELL, &[130, 59, 179, 195, 110, 135, 61, 2]));
+    assert!(eq(&CREATE_ATA, &[22, 51, 53, 97, 247, 184, 54, 78]));
+    assert!(eq(&EXPIRED_SLOT, &[169, 134, 33, 62, 168, 2, 246, 176]));
+    assert!(eq(&RAYDIUM_SELL, &[183, 77, 232, 39, 117, 138, 183, 72]));
+    assert!(eq(&pump::BUY, &[102, 6, 61, 18, 1, 218, 235, 234]));
+    assert!(eq(&pump::SELL, &[51, 230, 133, 164, 1, 127, 131, 173]));
+};
@@ -0,0 +1,56 @@
+use amm_proxy_interface::UnpackError;
+use solana_program::program_error::ProgramError;
+
+/// 代理合约自身的错误，错误码固定不变，便于链下区分是代理拒绝还是下游 DEX 失败
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ProxyError {
+    /// 已超过过期槽位（保留原 `MyError::SlotExpired` 的错误码 0）
+    SlotExpired = 0,
+    /// 指令数据长度不足
+    InstructionDataTooShort = 1,
+    /// 指令数据格式错误或长度不匹配
+    InvalidInstructionData = 2,
+    /// 账户数量不足
+    NotEnoughAccountKeys = 3,
+    /// 未知的指令选择器
+    UnknownSelector = 4,
+    /// 实际收到的数量低于 `min_out`
+    SlippageExceeded = 5,
+    /// 目标程序不在允许列表中
+    ProgramNotAllowed = 6,
+    /// 用于计量余额的账户不是有效的代币账户
+    InvalidTokenAccount = 7,
+    /// 来源账户实际减少的数量超过 `max_in`
+    MaxInputExceeded = 8,
+    /// 已超过截止时间戳
+    TimestampExpired = 9,
+    /// 尚未到达最早槽位
+    SlotNotReached = 10,
+    /// 尚未到达最早时间戳
+    TimestampNotReached = 11,
+    /// 账户数量超出该指令的账户表
+    UnexpectedAccount = 12,
+    /// 传入的账户与按种子派生的地址不一致
+    InvalidDerivedAccount = 13,
+    /// 账户不属于预期程序或数据长度不足，无法读取
+    InvalidAccountData = 14,
+    /// 账户列表仍是 Pump / PumpAMM 的旧版布局（缺少 creator vault、交易量累加器或 fee_config）
+    StaleAccountLayout = 15,
+}
+
+impl From<ProxyError> for ProgramError {
+    fn from(e: ProxyError) -> Self {
+        ProgramError::Custom(e as u32) // 自定义错误码更清晰
+    }
+}
+
+impl From<UnpackError> for ProxyError {
+    fn from(e: UnpackError) -> Self {
+        match e {
+            UnpackError::DataTooShort => ProxyError::InstructionDataTooShort,
+            UnpackError::InvalidData => ProxyError::InvalidInstructionData,
+            UnpackError::UnknownSelector => ProxyError::UnknownSelector,
+        }
+    }
+}
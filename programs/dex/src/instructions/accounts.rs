@@ -0,0 +1,212 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::AccountMeta,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+
+/// 账户在下游指令中允许的最大权限
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountRole {
+    pub writable: bool,
+    pub signer: bool,
+}
+
+/// 只读
+pub const R: AccountRole = AccountRole {
+    writable: false,
+    signer: false,
+};
+/// 可写
+pub const W: AccountRole = AccountRole {
+    writable: true,
+    signer: false,
+};
+/// 只读签名
+pub const S: AccountRole = AccountRole {
+    writable: false,
+    signer: true,
+};
+/// 可写且签名（用户钱包）
+pub const WS: AccountRole = AccountRole {
+    writable: true,
+    signer: true,
+};
+
+/// 按账户表生成 CPI 的账户元数据。
+///
+/// 转发的权限取交易中的实际权限与账户表的交集，交易里多余的签名或可写权限不会传给下游；
+/// 账户数量少于 `required` 或多于账户表长度时直接拒绝。
+pub fn to_account_metas(
+    accounts: &[AccountInfo],
+    schema: &[AccountRole],
+    required: usize,
+) -> Result<Vec<AccountMeta>, ProgramError> {
+    if accounts.len() < required {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    }
+    if accounts.len() > schema.len() {
+        return Err(ProxyError::UnexpectedAccount.into());
+    }
+
+    Ok(accounts
+        .iter()
+        .zip(schema)
+        .map(|(acc, role)| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.is_signer && role.signer,
+            is_writable: acc.is_writable && role.writable,
+        })
+        .collect())
+}
+
+/// 固定账户表之后跟随数量可变的账户（如 tick array、bin array），统一按 `remaining` 的权限转发，
+/// 至少需要 `min_remaining` 个。
+pub fn to_account_metas_with_remaining(
+    accounts: &[AccountInfo],
+    schema: &[AccountRole],
+    remaining: AccountRole,
+    min_remaining: usize,
+) -> Result<Vec<AccountMeta>, ProgramError> {
+    if accounts.len() < schema.len() + min_remaining {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    }
+
+    Ok(accounts
+        .iter()
+        .zip(schema.iter().chain(core::iter::repeat(&remaining)))
+        .map(|(acc, role)| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.is_signer && role.signer,
+            is_writable: acc.is_writable && role.writable,
+        })
+        .collect())
+}
+
+/// 校验账户地址等于 `program_id` 下由 `seeds` 派生的 PDA
+pub fn check_derived_address(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let (expected, _) = Pubkey::find_program_address(seeds, program_id);
+    if *account.key != expected {
+        return Err(ProxyError::InvalidDerivedAccount.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fake {
+        key: Pubkey,
+        lamports: u64,
+        signer: bool,
+        writable: bool,
+    }
+
+    fn fakes(flags: &[(bool, bool)]) -> Vec<Fake> {
+        flags
+            .iter()
+            .map(|&(signer, writable)| Fake {
+                key: Pubkey::new_unique(),
+                lamports: 0,
+                signer,
+                writable,
+            })
+            .collect()
+    }
+
+    fn infos(fakes: &mut [Fake]) -> Vec<AccountInfo<'_>> {
+        static OWNER: Pubkey = Pubkey::new_from_array([0; 32]);
+        fakes
+            .iter_mut()
+            .map(|fake| {
+                AccountInfo::new(
+                    &fake.key,
+                    fake.signer,
+                    fake.writable,
+                    &mut fake.lamports,
+                    &mut [],
+                    &OWNER,
+                    false,
+                    0,
+                )
+            })
+            .collect()
+    }
+
+    fn flags(metas: &[AccountMeta]) -> Vec<(bool, bool)> {
+        metas
+            .iter()
+            .map(|meta| (meta.is_signer, meta.is_writable))
+            .collect()
+    }
+
+    #[test]
+    fn extra_privileges_are_dropped() {
+        let mut fakes = fakes(&[(true, true), (true, true), (true, true), (true, true)]);
+        let accounts = infos(&mut fakes);
+
+        let metas = to_account_metas(&accounts, &[WS, W, S, R], 4).unwrap();
+        assert_eq!(
+            flags(&metas),
+            [(true, true), (false, true), (true, false), (false, false)]
+        );
+        assert!(metas.iter().zip(&accounts).all(|(m, a)| m.pubkey == *a.key));
+
+        let metas = to_account_metas_with_remaining(&accounts, &[WS, R], W, 2).unwrap();
+        assert_eq!(
+            flags(&metas),
+            [(true, true), (false, false), (false, true), (false, true)]
+        );
+    }
+
+    #[test]
+    fn missing_privileges_are_not_granted() {
+        let mut fakes = fakes(&[(false, false), (false, true), (true, false)]);
+        let accounts = infos(&mut fakes);
+
+        let metas = to_account_metas(&accounts, &[WS, WS, WS], 3).unwrap();
+        assert_eq!(
+            flags(&metas),
+            [(false, false), (false, true), (true, false)]
+        );
+
+        let metas = to_account_metas_with_remaining(&accounts, &[WS], WS, 0).unwrap();
+        assert_eq!(
+            flags(&metas),
+            [(false, false), (false, true), (true, false)]
+        );
+    }
+
+    #[test]
+    fn account_count_is_checked() {
+        let mut fakes = fakes(&[(false, false); 3]);
+        let accounts = infos(&mut fakes);
+
+        assert_eq!(
+            to_account_metas(&accounts, &[R, R], 2),
+            Err(ProxyError::UnexpectedAccount.into())
+        );
+        assert_eq!(
+            to_account_metas(&accounts, &[R; 5], 4),
+            Err(ProxyError::NotEnoughAccountKeys.into())
+        );
+        // 可选的尾部账户可以省略
+        assert_eq!(to_account_metas(&accounts, &[R; 5], 3).unwrap().len(), 3);
+
+        assert_eq!(
+            to_account_metas_with_remaining(&accounts, &[R, R], W, 2),
+            Err(ProxyError::NotEnoughAccountKeys.into())
+        );
+        assert_eq!(
+            to_account_metas_with_remaining(&accounts, &[R, R], W, 1)
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
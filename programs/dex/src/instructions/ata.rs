@@ -1,3 +1,4 @@
+use amm_proxy_interface::instruction::CreateAtaArgs;
 use arrayref::array_ref;
 use solana_program::{
     account_info::AccountInfo,
@@ -7,31 +8,55 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-pub const ATA_SELECTOR: &[u8; 8] = &[22, 51, 53, 97, 247, 184, 54, 78];
+use crate::error::ProxyError;
+
+pub const ATA_PROGRAM: Pubkey =
+    solana_program::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// 由 `funder` 付款并作为所有者创建关联代币账户
+pub fn create_associated_token_account_instruction(
+    funder: &Pubkey,
+    associated_token_account: &Pubkey,
+    mint: &Pubkey,
+    system_program: &Pubkey,
+    token_program: &Pubkey,
+    idempotent: bool,
+) -> Instruction {
+    Instruction {
+        program_id: ATA_PROGRAM,
+        accounts: vec![
+            AccountMeta::new(*funder, true),
+            AccountMeta::new(*associated_token_account, false),
+            AccountMeta::new_readonly(*funder, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*system_program, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        // 0: Create，1: CreateIdempotent
+        data: vec![idempotent as u8],
+    }
+}
 
-const ATA_PROGRAM: Pubkey = solana_program::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
 pub fn process_create_associated_token_account(
     accounts: &[AccountInfo],
-    instruction_data: &[u8],
+    args: &CreateAtaArgs,
 ) -> ProgramResult {
+    if accounts.len() < 5 {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    }
+
     let [funder_info, associated_token_account_info, spl_token_mint_info, system_program_info, spl_token_program_info] =
         array_ref![accounts, 0, 5];
 
-    let funder_key = *funder_info.key;
-
     invoke_unchecked(
-        &Instruction {
-            program_id: ATA_PROGRAM,
-            accounts: vec![
-                AccountMeta::new(funder_key, true),
-                AccountMeta::new(*associated_token_account_info.key, false),
-                AccountMeta::new_readonly(funder_key, false),
-                AccountMeta::new_readonly(*spl_token_mint_info.key, false),
-                AccountMeta::new_readonly(*system_program_info.key, false),
-                AccountMeta::new_readonly(*spl_token_program_info.key, false),
-            ],
-            data: instruction_data.to_vec(),
-        },
+        &create_associated_token_account_instruction(
+            funder_info.key,
+            associated_token_account_info.key,
+            spl_token_mint_info.key,
+            system_program_info.key,
+            spl_token_program_info.key,
+            args.idempotent,
+        ),
         accounts,
     )
 }
@@ -0,0 +1,323 @@
+use amm_proxy_interface::instruction::SwapGuard;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program::invoke_unchecked, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::slot::check_deadline;
+
+// SPL Token / Token-2022 账户中 amount 字段的偏移
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+// 只有这两个程序拥有的账户才按代币账户布局读取 amount
+const TOKEN_PROGRAMS: [Pubkey; 2] = [
+    pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"),
+    pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"),
+];
+
+/// CPI 前后用于计量的余额来源
+#[derive(Clone, Copy)]
+pub enum Balance<'a, 'info> {
+    /// 代币账户的 amount
+    Token(&'a AccountInfo<'info>),
+    /// 账户的 lamports（SOL 输出直接到钱包时使用）
+    Lamports(&'a AccountInfo<'info>),
+}
+
+impl Balance<'_, '_> {
+    pub fn amount(&self) -> Result<u64, ProgramError> {
+        match self {
+            Balance::Token(account) => {
+                if !TOKEN_PROGRAMS.contains(account.owner) {
+                    return Err(ProxyError::InvalidTokenAccount.into());
+                }
+                let data = account.try_borrow_data()?;
+                let amount = data
+                    .get(TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8)
+                    .ok_or(ProxyError::InvalidTokenAccount)?;
+                Ok(u64::from_le_bytes(amount.try_into().unwrap()))
+            }
+            Balance::Lamports(account) => Ok(account.lamports()),
+        }
+    }
+}
+
+/// 调用方传入的目标程序必须在允许列表中，避免把用户签名权限转交给任意程序
+pub fn check_program_id(program: &AccountInfo, allowed: &[Pubkey]) -> ProgramResult {
+    if !allowed.contains(program.key) {
+        return Err(ProxyError::ProgramNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// 检查截止条件后执行 CPI，并按 `guard` 校验 `source` 的实际减少量与 `destination` 的实际增加量
+pub fn invoke_guarded(
+    guard: &SwapGuard,
+    source: Balance,
+    destination: Balance,
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    check_deadline(&guard.deadline)?;
+
+    let check_in = guard.max_in != u64::MAX;
+    let check_out = guard.min_out != 0;
+
+    // 未设置约束时不读取余额，节省 CU
+    if !check_in && !check_out {
+        return invoke_unchecked(instruction, accounts);
+    }
+
+    let source_before = if check_in { source.amount()? } else { 0 };
+    let destination_before = if check_out { destination.amount()? } else { 0 };
+
+    invoke_unchecked(instruction, accounts)?;
+
+    if check_in && source_before.saturating_sub(source.amount()?) > guard.max_in {
+        return Err(ProxyError::MaxInputExceeded.into());
+    }
+    if check_out && destination.amount()?.saturating_sub(destination_before) < guard.min_out {
+        return Err(ProxyError::SlippageExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// `SwapGuard` 之外按账户单独设置的余额约束，用于同时涉及多个代币账户的指令（如添加/移除流动性）
+#[derive(Clone, Copy)]
+pub enum BalanceLimit<'a, 'info> {
+    /// 实际减少量不超过上限，`u64::MAX` 表示不检查
+    MaxDecrease(Balance<'a, 'info>, u64),
+    /// 实际增加量不低于下限，0 表示不检查
+    MinIncrease(Balance<'a, 'info>, u64),
+}
+
+impl<'a, 'info> BalanceLimit<'a, 'info> {
+    fn balance(&self) -> Option<Balance<'a, 'info>> {
+        match *self {
+            BalanceLimit::MaxDecrease(balance, max) if max != u64::MAX => Some(balance),
+            BalanceLimit::MinIncrease(balance, min) if min != 0 => Some(balance),
+            _ => None,
+        }
+    }
+
+    fn check(&self, before: u64, after: u64) -> ProgramResult {
+        match *self {
+            BalanceLimit::MaxDecrease(_, max) if before.saturating_sub(after) > max => {
+                Err(ProxyError::MaxInputExceeded.into())
+            }
+            BalanceLimit::MinIncrease(_, min) if after.saturating_sub(before) < min => {
+                Err(ProxyError::SlippageExceeded.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 与 `invoke_guarded` 相同，并在 CPI 后逐个校验 `limits` 中的余额约束
+pub fn invoke_guarded_with_limits(
+    guard: &SwapGuard,
+    source: Balance,
+    destination: Balance,
+    limits: &[BalanceLimit],
+    instruction: &Instruction,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let before = limits
+        .iter()
+        .map(|limit| limit.balance().map_or(Ok(0), |balance| balance.amount()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    invoke_guarded(guard, source, destination, instruction, accounts)?;
+
+    for (limit, before) in limits.iter().zip(before) {
+        if let Some(balance) = limit.balance() {
+            limit.check(before, balance.amount()?)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amm_proxy_interface::instruction::Deadline;
+
+    // 默认的 syscall 桩中 CPI 直接返回成功且不改动任何账户，相当于一次没有成交的交换
+    fn account<'a>(
+        key: &'a Pubkey,
+        owner: &'a Pubkey,
+        lamports: &'a mut u64,
+        data: &'a mut [u8],
+    ) -> AccountInfo<'a> {
+        AccountInfo::new(key, false, true, lamports, data, owner, false, 0)
+    }
+
+    fn token_data(amount: u64) -> Vec<u8> {
+        let mut data = vec![0; 165];
+        data[TOKEN_AMOUNT_OFFSET..TOKEN_AMOUNT_OFFSET + 8].copy_from_slice(&amount.to_le_bytes());
+        data
+    }
+
+    fn noop_instruction() -> Instruction {
+        Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![])
+    }
+
+    fn guard(min_out: u64, max_in: u64) -> SwapGuard {
+        SwapGuard {
+            min_out,
+            max_in,
+            deadline: Deadline::NONE,
+        }
+    }
+
+    #[test]
+    fn short_fill_is_rejected() {
+        let (source_key, destination_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut source_lamports, mut destination_lamports) = (0, 0);
+        let mut source_data = token_data(100);
+        let mut destination_data = token_data(5);
+        let source = account(
+            &source_key,
+            &TOKEN_PROGRAMS[0],
+            &mut source_lamports,
+            &mut source_data,
+        );
+        let destination = account(
+            &destination_key,
+            &TOKEN_PROGRAMS[1],
+            &mut destination_lamports,
+            &mut destination_data,
+        );
+
+        assert_eq!(
+            invoke_guarded(
+                &guard(1, u64::MAX),
+                Balance::Token(&source),
+                Balance::Token(&destination),
+                &noop_instruction(),
+                &[],
+            ),
+            Err(ProxyError::SlippageExceeded.into())
+        );
+        assert_eq!(
+            invoke_guarded(
+                &guard(0, 0),
+                Balance::Token(&source),
+                Balance::Lamports(&destination),
+                &noop_instruction(),
+                &[],
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn unset_min_out_skips_balance_reads() {
+        let key = Pubkey::new_unique();
+        let system_program = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = [];
+        // 既不属于代币程序、也没有数据，一旦读取余额就会失败
+        let invalid = account(&key, &system_program, &mut lamports, &mut data);
+
+        assert_eq!(
+            invoke_guarded(
+                &SwapGuard::NONE,
+                Balance::Token(&invalid),
+                Balance::Token(&invalid),
+                &noop_instruction(),
+                &[],
+            ),
+            Ok(())
+        );
+
+        let source_key = Pubkey::new_unique();
+        let mut source_lamports = 0;
+        let mut source_data = token_data(100);
+        let source = account(
+            &source_key,
+            &TOKEN_PROGRAMS[0],
+            &mut source_lamports,
+            &mut source_data,
+        );
+        assert_eq!(
+            invoke_guarded(
+                &guard(0, 10),
+                Balance::Token(&source),
+                Balance::Token(&invalid),
+                &noop_instruction(),
+                &[],
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn token_balance_requires_token_program_owner() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = token_data(100);
+        let spoofed = account(&key, &owner, &mut lamports, &mut data);
+
+        assert_eq!(
+            Balance::Token(&spoofed).amount(),
+            Err(ProxyError::InvalidTokenAccount.into())
+        );
+        assert_eq!(
+            invoke_guarded(
+                &guard(1, u64::MAX),
+                Balance::Token(&spoofed),
+                Balance::Token(&spoofed),
+                &noop_instruction(),
+                &[],
+            ),
+            Err(ProxyError::InvalidTokenAccount.into())
+        );
+    }
+
+    #[test]
+    fn short_token_account_is_an_error() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [0; TOKEN_AMOUNT_OFFSET + 7];
+        let short = account(&key, &TOKEN_PROGRAMS[0], &mut lamports, &mut data);
+
+        assert_eq!(
+            Balance::Token(&short).amount(),
+            Err(ProxyError::InvalidTokenAccount.into())
+        );
+    }
+
+    #[test]
+    fn balance_limit_boundaries() {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = token_data(0);
+        let info = account(&key, &TOKEN_PROGRAMS[0], &mut lamports, &mut data);
+        let balance = Balance::Token(&info);
+
+        let max = BalanceLimit::MaxDecrease(balance, 10);
+        assert_eq!(max.check(100, 90), Ok(()));
+        assert_eq!(max.check(100, 89), Err(ProxyError::MaxInputExceeded.into()));
+
+        let min = BalanceLimit::MinIncrease(balance, 10);
+        assert_eq!(min.check(100, 110), Ok(()));
+        assert_eq!(
+            min.check(100, 109),
+            Err(ProxyError::SlippageExceeded.into())
+        );
+
+        // u64::MAX 上限与 0 下限表示不检查，不读取该账户
+        let unlimited = BalanceLimit::MaxDecrease(balance, u64::MAX);
+        assert!(unlimited.balance().is_none());
+        assert_eq!(unlimited.check(u64::MAX, 0), Ok(()));
+        let unbounded = BalanceLimit::MinIncrease(balance, 0);
+        assert!(unbounded.balance().is_none());
+        assert_eq!(unbounded.check(100, 0), Ok(()));
+        assert!(max.balance().is_some() && min.balance().is_some());
+    }
+}
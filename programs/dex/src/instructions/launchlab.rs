@@ -0,0 +1,97 @@
+use amm_proxy_interface::{instruction::LaunchLabArgs, selector::launchlab};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas_with_remaining, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+#[cfg(not(feature = "devnet"))]
+const LAUNCHLAB_PROGRAMS: [Pubkey; 1] = [pubkey!("LanMV9sAd7wArD4vJFi2qDdfnVhFxYSUg6eADduJ3uj")];
+#[cfg(feature = "devnet")]
+const LAUNCHLAB_PROGRAMS: [Pubkey; 1] = [pubkey!("LanD8FpTBBvzZFXjTxsAoipkFsxPUCDB4qAqKxYDiNP")];
+
+// 鉴别器 + amount + other_amount_threshold + share_fee_rate
+const ARG_LEN: usize = 32;
+
+const LAUNCHLAB_ACCOUNTS_LEN: usize = 15;
+
+// payer, authority, global_config, platform_config, pool_state, user_base_token,
+// user_quote_token, base_vault, quote_vault, base_token_mint, quote_token_mint,
+// base_token_program, quote_token_program, event_authority, program
+const LAUNCHLAB_ACCOUNTS: [AccountRole; LAUNCHLAB_ACCOUNTS_LEN] =
+    [S, R, R, R, W, W, W, W, W, R, R, R, R, R, R];
+
+const LAUNCHLAB_USER_BASE_INDEX: usize = 5;
+const LAUNCHLAB_USER_QUOTE_INDEX: usize = 6;
+
+// 账户顺序：launchlab_program + 买卖共用的 15 个账户，之后为可选的可写账户（share fee 接收账户、
+// 新版的 fee vault 等）；share_fee_rate 非零时至少需要一个。买入时 quote 为来源、base 为目标，卖出相反
+fn invoke_trade(
+    accounts: &[AccountInfo],
+    discriminator: &[u8; 8],
+    args: &LaunchLabArgs,
+    buy: bool,
+) -> ProgramResult {
+    let Some((launchlab_program, trade_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(launchlab_program, &LAUNCHLAB_PROGRAMS)?;
+
+    let min_remaining = (args.share_fee_rate != 0) as usize;
+    let metas =
+        to_account_metas_with_remaining(trade_accounts, &LAUNCHLAB_ACCOUNTS, W, min_remaining)?;
+
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&args.amount.to_le_bytes());
+    data.extend_from_slice(&args.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&args.share_fee_rate.to_le_bytes());
+
+    let base = &trade_accounts[LAUNCHLAB_USER_BASE_INDEX];
+    let quote = &trade_accounts[LAUNCHLAB_USER_QUOTE_INDEX];
+    let (source, destination) = if buy { (quote, base) } else { (base, quote) };
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(source),
+        Balance::Token(destination),
+        &Instruction {
+            program_id: *launchlab_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
+
+pub fn process_launchlab_buy_exact_in(
+    accounts: &[AccountInfo],
+    args: &LaunchLabArgs,
+) -> ProgramResult {
+    invoke_trade(accounts, &launchlab::BUY_EXACT_IN, args, true)
+}
+
+pub fn process_launchlab_buy_exact_out(
+    accounts: &[AccountInfo],
+    args: &LaunchLabArgs,
+) -> ProgramResult {
+    invoke_trade(accounts, &launchlab::BUY_EXACT_OUT, args, true)
+}
+
+pub fn process_launchlab_sell_exact_in(
+    accounts: &[AccountInfo],
+    args: &LaunchLabArgs,
+) -> ProgramResult {
+    invoke_trade(accounts, &launchlab::SELL_EXACT_IN, args, false)
+}
+
+pub fn process_launchlab_sell_exact_out(
+    accounts: &[AccountInfo],
+    args: &LaunchLabArgs,
+) -> ProgramResult {
+    invoke_trade(accounts, &launchlab::SELL_EXACT_OUT, args, false)
+}
@@ -0,0 +1,155 @@
+use amm_proxy_interface::{instruction::SwapExactInArgs, selector::meteora_damm};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{
+    check_derived_address, to_account_metas, AccountRole, R, S, W,
+};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+// 以下程序 ID 主网与 devnet 相同
+const METEORA_DAMM_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB")];
+const METEORA_VAULT_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi")];
+const METEORA_DAMM_V2_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG")];
+
+// 鉴别器 + amount_in + minimum_amount_out
+const ARG_LEN: usize = 24;
+
+const DAMM_ACCOUNTS_LEN: usize = 15;
+const DAMM_V2_ACCOUNTS_LEN: usize = 14;
+
+// pool, user_source_token, user_destination_token, a_vault, b_vault, a_token_vault,
+// b_token_vault, a_vault_lp_mint, b_vault_lp_mint, a_vault_lp, b_vault_lp, protocol_token_fee,
+// user, vault_program, token_program
+const DAMM_ACCOUNTS: [AccountRole; DAMM_ACCOUNTS_LEN] =
+    [W, W, W, W, W, W, W, W, W, W, W, W, S, R, R];
+
+const DAMM_POOL_INDEX: usize = 0;
+const DAMM_USER_SOURCE_INDEX: usize = 1;
+const DAMM_USER_DESTINATION_INDEX: usize = 2;
+const DAMM_A_VAULT_INDEX: usize = 3;
+const DAMM_VAULT_PROGRAM_INDEX: usize = 13;
+
+// pool_authority, pool, input_token_account, output_token_account, token_a_vault, token_b_vault,
+// token_a_mint, token_b_mint, payer, token_a_program, token_b_program, referral_token_account,
+// event_authority, program
+// 不使用 referral_token_account 时传 cp-amm 程序 ID
+const DAMM_V2_ACCOUNTS: [AccountRole; DAMM_V2_ACCOUNTS_LEN] =
+    [R, W, W, W, W, W, R, R, S, R, R, W, R, R];
+
+const DAMM_V2_POOL_AUTHORITY_INDEX: usize = 0;
+const DAMM_V2_INPUT_TOKEN_INDEX: usize = 2;
+const DAMM_V2_OUTPUT_TOKEN_INDEX: usize = 3;
+const DAMM_V2_EVENT_AUTHORITY_INDEX: usize = 12;
+
+// 每一侧 vault 派生出的 token_vault、lp_mint（vault 程序）与池子持有的 vault LP（DAMM 程序），
+// a、b 两侧在账户表中的间隔为 1
+fn check_vault_accounts(
+    accounts: &[AccountInfo],
+    side: usize,
+    damm_program: &Pubkey,
+) -> ProgramResult {
+    let vault_program = accounts[DAMM_VAULT_PROGRAM_INDEX].key;
+    let vault = accounts[DAMM_A_VAULT_INDEX + side].key.as_ref();
+    let pool = accounts[DAMM_POOL_INDEX].key.as_ref();
+
+    check_derived_address(
+        &accounts[DAMM_A_VAULT_INDEX + 2 + side],
+        &[b"token_vault", vault],
+        vault_program,
+    )?;
+    check_derived_address(
+        &accounts[DAMM_A_VAULT_INDEX + 4 + side],
+        &[b"lp_mint", vault],
+        vault_program,
+    )?;
+    check_derived_address(
+        &accounts[DAMM_A_VAULT_INDEX + 6 + side],
+        &[vault, pool],
+        damm_program,
+    )
+}
+
+fn swap_data(args: &SwapExactInArgs) -> Vec<u8> {
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(&meteora_damm::SWAP);
+    data.extend_from_slice(&args.amount_in.to_le_bytes());
+    data.extend_from_slice(&args.minimum_amount_out.to_le_bytes());
+    data
+}
+
+// 账户顺序：damm_program + DAMM v1 swap 的 15 个账户（含两侧 vault 的 LP 账户）
+pub fn process_meteora_damm_swap(
+    accounts: &[AccountInfo],
+    args: &SwapExactInArgs,
+) -> ProgramResult {
+    let Some((damm_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(damm_program, &METEORA_DAMM_PROGRAMS)?;
+
+    let metas = to_account_metas(swap_accounts, &DAMM_ACCOUNTS, DAMM_ACCOUNTS_LEN)?;
+
+    check_program_id(
+        &swap_accounts[DAMM_VAULT_PROGRAM_INDEX],
+        &METEORA_VAULT_PROGRAMS,
+    )?;
+    check_vault_accounts(swap_accounts, 0, damm_program.key)?;
+    check_vault_accounts(swap_accounts, 1, damm_program.key)?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&swap_accounts[DAMM_USER_SOURCE_INDEX]),
+        Balance::Token(&swap_accounts[DAMM_USER_DESTINATION_INDEX]),
+        &Instruction {
+            program_id: *damm_program.key,
+            accounts: metas,
+            data: swap_data(args),
+        },
+        accounts,
+    )
+}
+
+// 账户顺序：cp_amm_program + DAMM v2 swap 的 14 个账户
+pub fn process_meteora_damm_v2_swap(
+    accounts: &[AccountInfo],
+    args: &SwapExactInArgs,
+) -> ProgramResult {
+    let Some((cp_amm_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(cp_amm_program, &METEORA_DAMM_V2_PROGRAMS)?;
+
+    let metas = to_account_metas(swap_accounts, &DAMM_V2_ACCOUNTS, DAMM_V2_ACCOUNTS_LEN)?;
+
+    check_derived_address(
+        &swap_accounts[DAMM_V2_POOL_AUTHORITY_INDEX],
+        &[b"pool_authority"],
+        cp_amm_program.key,
+    )?;
+    check_derived_address(
+        &swap_accounts[DAMM_V2_EVENT_AUTHORITY_INDEX],
+        &[b"__event_authority"],
+        cp_amm_program.key,
+    )?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&swap_accounts[DAMM_V2_INPUT_TOKEN_INDEX]),
+        Balance::Token(&swap_accounts[DAMM_V2_OUTPUT_TOKEN_INDEX]),
+        &Instruction {
+            program_id: *cp_amm_program.key,
+            accounts: metas,
+            data: swap_data(args),
+        },
+        accounts,
+    )
+}
@@ -0,0 +1,86 @@
+use amm_proxy_interface::{
+    instruction::{SwapExactInArgs, SwapExactOutArgs, SwapGuard},
+    selector::meteora_dlmm,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas_with_remaining, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+// 主网与 devnet 使用同一个程序 ID
+const METEORA_DLMM_PROGRAMS: [Pubkey; 1] = [pubkey!("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9t6dj8fKK")];
+
+const ARG_LEN: usize = 24;
+
+// lb_pair, bin_array_bitmap_extension, reserve_x, reserve_y, user_token_in, user_token_out,
+// token_x_mint, token_y_mint, oracle, host_fee_in, user, token_x_program, token_y_program,
+// event_authority, program
+// 可选账户（bitmap extension、host_fee_in）不使用时传 DLMM 程序 ID
+const DLMM_ACCOUNTS: [AccountRole; 15] = [W, R, W, W, W, W, R, R, W, W, S, R, R, R, R];
+
+const DLMM_USER_TOKEN_IN_INDEX: usize = 4;
+const DLMM_USER_TOKEN_OUT_INDEX: usize = 5;
+
+// 账户顺序：dlmm_program + swap 的 15 个固定账户 + 可变数量的 bin array（至少一个）
+fn invoke_swap(
+    accounts: &[AccountInfo],
+    discriminator: &[u8; 8],
+    amount: u64,
+    limit: u64,
+    guard: &SwapGuard,
+) -> ProgramResult {
+    let Some((dlmm_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(dlmm_program, &METEORA_DLMM_PROGRAMS)?;
+
+    let metas = to_account_metas_with_remaining(swap_accounts, &DLMM_ACCOUNTS, W, 1)?;
+
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&limit.to_le_bytes());
+
+    invoke_guarded(
+        guard,
+        Balance::Token(&swap_accounts[DLMM_USER_TOKEN_IN_INDEX]),
+        Balance::Token(&swap_accounts[DLMM_USER_TOKEN_OUT_INDEX]),
+        &Instruction {
+            program_id: *dlmm_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
+
+pub fn process_meteora_dlmm_swap(
+    accounts: &[AccountInfo],
+    args: &SwapExactInArgs,
+) -> ProgramResult {
+    invoke_swap(
+        accounts,
+        &meteora_dlmm::SWAP,
+        args.amount_in,
+        args.minimum_amount_out,
+        &args.guard,
+    )
+}
+
+pub fn process_meteora_dlmm_swap_exact_out(
+    accounts: &[AccountInfo],
+    args: &SwapExactOutArgs,
+) -> ProgramResult {
+    invoke_swap(
+        accounts,
+        &meteora_dlmm::SWAP_EXACT_OUT,
+        args.max_amount_in,
+        args.amount_out,
+        &args.guard,
+    )
+}
@@ -1,4 +1,15 @@
+pub mod accounts;
 pub mod ata;
+pub mod guard;
+pub mod launchlab;
+pub mod meteora_damm;
+pub mod meteora_dlmm;
+pub mod moonshot;
+pub mod orca;
+pub mod orderbook;
 pub mod pump;
 pub mod raydium;
+pub mod raydium_clmm;
+pub mod raydium_cpmm;
 pub mod slot;
+pub mod token_swap;
@@ -0,0 +1,78 @@
+use amm_proxy_interface::{instruction::MoonshotTradeArgs, selector::moonshot};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas, AccountRole, R, W, WS};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+// 主网与 devnet 使用同一个程序 ID
+const MOONSHOT_PROGRAMS: [Pubkey; 1] = [pubkey!("MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG")];
+
+// 鉴别器 + token_amount + collateral_amount + fixed_side + slippage_bps
+const ARG_LEN: usize = 33;
+
+const MOONSHOT_ACCOUNTS_LEN: usize = 11;
+
+// sender, sender_token_account, curve_account, curve_token_account, dex_fee, helio_fee, mint,
+// config_account, token_program, associated_token_program, system_program
+const MOONSHOT_ACCOUNTS: [AccountRole; MOONSHOT_ACCOUNTS_LEN] = [WS, W, W, W, W, W, R, R, R, R, R];
+
+const MOONSHOT_SENDER_INDEX: usize = 0;
+const MOONSHOT_SENDER_TOKEN_INDEX: usize = 1;
+
+// 账户顺序：moonshot_program + 买卖共用的 11 个账户
+fn moonshot_instruction(
+    accounts: &[AccountInfo],
+    discriminator: &[u8; 8],
+    args: &MoonshotTradeArgs,
+) -> Result<Instruction, ProgramError> {
+    let Some((moonshot_program, trade_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(moonshot_program, &MOONSHOT_PROGRAMS)?;
+
+    let metas = to_account_metas(trade_accounts, &MOONSHOT_ACCOUNTS, MOONSHOT_ACCOUNTS_LEN)?;
+
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&args.token_amount.to_le_bytes());
+    data.extend_from_slice(&args.collateral_amount.to_le_bytes());
+    data.push(args.fixed_side);
+    data.extend_from_slice(&args.slippage_bps.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: *moonshot_program.key,
+        accounts: metas,
+        data,
+    })
+}
+
+pub fn process_moonshot_buy(accounts: &[AccountInfo], args: &MoonshotTradeArgs) -> ProgramResult {
+    let instruction = moonshot_instruction(accounts, &moonshot::BUY, args)?;
+    let trade_accounts = &accounts[1..];
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Lamports(&trade_accounts[MOONSHOT_SENDER_INDEX]),
+        Balance::Token(&trade_accounts[MOONSHOT_SENDER_TOKEN_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+pub fn process_moonshot_sell(accounts: &[AccountInfo], args: &MoonshotTradeArgs) -> ProgramResult {
+    let instruction = moonshot_instruction(accounts, &moonshot::SELL, args)?;
+    let trade_accounts = &accounts[1..];
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&trade_accounts[MOONSHOT_SENDER_TOKEN_INDEX]),
+        Balance::Lamports(&trade_accounts[MOONSHOT_SENDER_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
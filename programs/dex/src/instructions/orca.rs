@@ -0,0 +1,103 @@
+use amm_proxy_interface::{instruction::OrcaSwapArgs, selector::orca};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+// 主网与 devnet 使用同一个程序 ID
+const WHIRLPOOL_PROGRAMS: [Pubkey; 1] = [pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc")];
+
+// 鉴别器 + amount + other_amount_threshold + sqrt_price_limit + amount_specified_is_input + a_to_b
+const ARG_LEN: usize = 42;
+
+const SWAP_ACCOUNTS_LEN: usize = 11;
+const SWAP_V2_ACCOUNTS_LEN: usize = 15;
+
+// token_program, token_authority, whirlpool, token_owner_account_a, token_vault_a,
+// token_owner_account_b, token_vault_b, tick_array_0, tick_array_1, tick_array_2, oracle
+const SWAP_ACCOUNTS: [AccountRole; SWAP_ACCOUNTS_LEN] = [R, S, W, W, W, W, W, W, W, W, W];
+
+// token_program_a, token_program_b, memo_program, token_authority, whirlpool, token_mint_a,
+// token_mint_b, token_owner_account_a, token_vault_a, token_owner_account_b, token_vault_b,
+// tick_array_0, tick_array_1, tick_array_2, oracle
+const SWAP_V2_ACCOUNTS: [AccountRole; SWAP_V2_ACCOUNTS_LEN] =
+    [R, R, R, S, W, R, R, W, W, W, W, W, W, W, W];
+
+const SWAP_OWNER_A_INDEX: usize = 3;
+const SWAP_OWNER_B_INDEX: usize = 5;
+const SWAP_V2_OWNER_A_INDEX: usize = 7;
+const SWAP_V2_OWNER_B_INDEX: usize = 9;
+
+fn swap_data(discriminator: &[u8; 8], args: &OrcaSwapArgs) -> Vec<u8> {
+    let mut data = Vec::with_capacity(ARG_LEN + 1);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&args.amount.to_le_bytes());
+    data.extend_from_slice(&args.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&args.sqrt_price_limit.to_le_bytes());
+    data.push(args.amount_specified_is_input as u8);
+    data.push(args.a_to_b as u8);
+    data
+}
+
+// 账户顺序：whirlpool_program + 对应指令的固定账户，owner_a / owner_b 是用户在两侧的代币账户
+fn invoke_swap(
+    accounts: &[AccountInfo],
+    data: Vec<u8>,
+    schema: &[AccountRole],
+    (owner_a, owner_b): (usize, usize),
+    args: &OrcaSwapArgs,
+) -> ProgramResult {
+    let Some((whirlpool_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(whirlpool_program, &WHIRLPOOL_PROGRAMS)?;
+
+    let metas = to_account_metas(swap_accounts, schema, schema.len())?;
+
+    let (source, destination) = if args.a_to_b {
+        (owner_a, owner_b)
+    } else {
+        (owner_b, owner_a)
+    };
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&swap_accounts[source]),
+        Balance::Token(&swap_accounts[destination]),
+        &Instruction {
+            program_id: *whirlpool_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
+
+pub fn process_orca_swap(accounts: &[AccountInfo], args: &OrcaSwapArgs) -> ProgramResult {
+    invoke_swap(
+        accounts,
+        swap_data(&orca::SWAP, args),
+        &SWAP_ACCOUNTS,
+        (SWAP_OWNER_A_INDEX, SWAP_OWNER_B_INDEX),
+        args,
+    )
+}
+
+// swap_v2 末尾的 remaining_accounts_info 固定传 None，不支持带 transfer hook 的代币
+pub fn process_orca_swap_v2(accounts: &[AccountInfo], args: &OrcaSwapArgs) -> ProgramResult {
+    let mut data = swap_data(&orca::SWAP_V2, args);
+    data.push(0);
+
+    invoke_swap(
+        accounts,
+        data,
+        &SWAP_V2_ACCOUNTS,
+        (SWAP_V2_OWNER_A_INDEX, SWAP_V2_OWNER_B_INDEX),
+        args,
+    )
+}
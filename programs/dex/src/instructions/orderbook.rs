@@ -0,0 +1,159 @@
+use amm_proxy_interface::{
+    instruction::{OpenBookTakeArgs, PhoenixTakeArgs},
+    selector::{openbook_v2, phoenix},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas, AccountRole, R, S, W, WS};
+use crate::instructions::guard::{
+    check_program_id, invoke_guarded, invoke_guarded_with_limits, Balance, BalanceLimit,
+};
+
+// 以下程序 ID 主网与 devnet 相同
+const OPENBOOK_V2_PROGRAMS: [Pubkey; 1] = [pubkey!("opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb")];
+const PHOENIX_PROGRAMS: [Pubkey; 1] = [pubkey!("PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY")];
+
+// OpenBook v2 PlaceOrderType::ImmediateOrCancel
+const OPENBOOK_ORDER_TYPE_IOC: u8 = 1;
+// 鉴别器 + side + price_lots + max_base_lots + max_quote_lots_including_fees + order_type + limit
+const OPENBOOK_ARG_LEN: usize = 35;
+
+const OPENBOOK_ACCOUNTS_LEN: usize = 16;
+
+// signer, penalty_payer, market, market_authority, bids, asks, market_base_vault,
+// market_quote_vault, event_heap, user_base_account, user_quote_account, oracle_a, oracle_b,
+// token_program, system_program, open_orders_admin
+// 可选账户（oracle、open_orders_admin）不使用时传 OpenBook v2 程序 ID
+const OPENBOOK_ACCOUNTS: [AccountRole; OPENBOOK_ACCOUNTS_LEN] =
+    [WS, WS, W, R, W, W, W, W, W, W, W, R, R, R, R, S];
+
+const OPENBOOK_USER_BASE_INDEX: usize = 9;
+const OPENBOOK_USER_QUOTE_INDEX: usize = 10;
+
+// OrderPacket::ImmediateOrCancel
+const PHOENIX_ORDER_PACKET_IOC: u8 = 2;
+// SelfTradeBehavior::CancelProvide
+const PHOENIX_SELF_TRADE_CANCEL_PROVIDE: u8 = 1;
+// 标签 + 变体 + side + Some(price) + 四个数量 + self_trade_behavior + None + client_order_id
+// + use_only_deposited_funds + 两个 None
+const PHOENIX_DATA_LEN: usize = 65;
+
+const PHOENIX_ACCOUNTS_LEN: usize = 9;
+
+// phoenix_program, log_authority, market, trader, base_account, quote_account, base_vault,
+// quote_vault, token_program
+const PHOENIX_ACCOUNTS: [AccountRole; PHOENIX_ACCOUNTS_LEN] = [R, R, W, S, W, W, W, W, R];
+
+const PHOENIX_USER_BASE_INDEX: usize = 4;
+const PHOENIX_USER_QUOTE_INDEX: usize = 5;
+
+// 买单花费 quote 得到 base，卖单相反
+fn taker_balances<'a, 'info>(
+    is_bid: bool,
+    base: &'a AccountInfo<'info>,
+    quote: &'a AccountInfo<'info>,
+) -> (Balance<'a, 'info>, Balance<'a, 'info>) {
+    if is_bid {
+        (Balance::Token(quote), Balance::Token(base))
+    } else {
+        (Balance::Token(base), Balance::Token(quote))
+    }
+}
+
+// 账户顺序：openbook_program + place_take_order 的 16 个账户。
+// 订单类型固定为 IOC，未成交部分不会挂单；OpenBook 本身没有最少成交量，
+// 由代理合约在 CPI 后按 min_amount_out 校验目标账户的实际到账，避免零成交也返回成功
+pub fn process_openbook_v2_take(
+    accounts: &[AccountInfo],
+    args: &OpenBookTakeArgs,
+) -> ProgramResult {
+    let Some((openbook_program, take_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(openbook_program, &OPENBOOK_V2_PROGRAMS)?;
+
+    let metas = to_account_metas(take_accounts, &OPENBOOK_ACCOUNTS, OPENBOOK_ACCOUNTS_LEN)?;
+
+    let mut data = Vec::with_capacity(OPENBOOK_ARG_LEN);
+    data.extend_from_slice(&openbook_v2::PLACE_TAKE_ORDER);
+    data.push(!args.is_bid as u8);
+    data.extend_from_slice(&args.price_lots.to_le_bytes());
+    data.extend_from_slice(&args.max_base_lots.to_le_bytes());
+    data.extend_from_slice(&args.max_quote_lots_including_fees.to_le_bytes());
+    data.push(OPENBOOK_ORDER_TYPE_IOC);
+    data.push(args.limit);
+
+    let (source, destination) = taker_balances(
+        args.is_bid,
+        &take_accounts[OPENBOOK_USER_BASE_INDEX],
+        &take_accounts[OPENBOOK_USER_QUOTE_INDEX],
+    );
+
+    invoke_guarded_with_limits(
+        &args.guard,
+        source,
+        destination,
+        &[BalanceLimit::MinIncrease(
+            destination,
+            args.min_amount_out.max(1),
+        )],
+        &Instruction {
+            program_id: *openbook_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
+
+// 账户顺序与 Phoenix Swap 相同，phoenix_program 本身即第一个账户。
+// 以 ImmediateOrCancel 订单包下单，未成交部分不会挂单
+pub fn process_phoenix_take(accounts: &[AccountInfo], args: &PhoenixTakeArgs) -> ProgramResult {
+    let metas = to_account_metas(accounts, &PHOENIX_ACCOUNTS, PHOENIX_ACCOUNTS_LEN)?;
+    let phoenix_program = &accounts[0];
+
+    check_program_id(phoenix_program, &PHOENIX_PROGRAMS)?;
+
+    let mut data = Vec::with_capacity(PHOENIX_DATA_LEN);
+    data.push(phoenix::SWAP);
+    data.push(PHOENIX_ORDER_PACKET_IOC);
+    data.push(!args.is_bid as u8);
+    data.push(1);
+    data.extend_from_slice(&args.price_in_ticks.to_le_bytes());
+    data.extend_from_slice(&args.num_base_lots.to_le_bytes());
+    data.extend_from_slice(&args.num_quote_lots.to_le_bytes());
+    data.extend_from_slice(&args.min_base_lots_to_fill.to_le_bytes());
+    data.extend_from_slice(&args.min_quote_lots_to_fill.to_le_bytes());
+    data.push(PHOENIX_SELF_TRADE_CANCEL_PROVIDE);
+    // match_limit
+    data.push(0);
+    data.extend_from_slice(&0u128.to_le_bytes());
+    // use_only_deposited_funds
+    data.push(0);
+    // last_valid_slot / last_valid_unix_timestamp_in_seconds，截止时间由 SwapGuard 负责
+    data.push(0);
+    data.push(0);
+
+    let (source, destination) = taker_balances(
+        args.is_bid,
+        &accounts[PHOENIX_USER_BASE_INDEX],
+        &accounts[PHOENIX_USER_QUOTE_INDEX],
+    );
+
+    invoke_guarded(
+        &args.guard,
+        source,
+        destination,
+        &Instruction {
+            program_id: *phoenix_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
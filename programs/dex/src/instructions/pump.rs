@@ -1,97 +1,868 @@
+use amm_proxy_interface::{
+    instruction::{
+        pack_string, PumpAmmBuyArgs, PumpAmmBuyExactQuoteInArgs, PumpAmmDepositArgs,
+        PumpAmmSellArgs, PumpAmmWithdrawArgs, PumpBuyArgs, PumpBuyExactSolArgs, PumpLaunchArgs,
+        PumpSellArgs, PumpTradeArgs,
+    },
+    selector,
+};
+use arrayref::array_ref;
 use solana_program::{
-    account_info::AccountInfo,
-    entrypoint::ProgramResult,
-    instruction::{AccountMeta, Instruction},
-    program::invoke_unchecked,
-    pubkey,
-    pubkey::Pubkey,
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program::invoke_unchecked, program_error::ProgramError, pubkey, pubkey::Pubkey,
 };
 
-const PUMPFUN_BUY_SELECTOR: &[u8; 8] = &[102, 6, 61, 18, 1, 218, 235, 234];
-const PUMPFUN_SELL_SELECTOR: &[u8; 8] = &[51, 230, 133, 164, 1, 127, 131, 173];
-const PUMPAMM_BUY_SELECTOR: &[u8; 8] = &[102, 6, 61, 18, 1, 218, 235, 234];
-const PUMPAMM_SELL_SELECTOR: &[u8; 8] = &[51, 230, 133, 164, 1, 127, 131, 173];
-
-pub const PUMP_SELECTOR: &[u8; 8] = &[82, 225, 119, 231, 78, 29, 45, 70];
-pub const PUMP_AMM_SELECTOR: &[u8; 8] = &[129, 59, 179, 195, 110, 135, 61, 2];
-pub const PUMP_SELL_SELECTOR: &[u8; 8] = &[83, 225, 119, 231, 78, 29, 45, 70];
-pub const PUMP_AMM_SELL_SELECTOR: &[u8; 8] = &[130, 59, 179, 195, 110, 135, 61, 2];
+use crate::error::ProxyError;
+use crate::instructions::accounts::{
+    check_derived_address, to_account_metas, AccountRole, R, W, WS,
+};
+use crate::instructions::ata::{create_associated_token_account_instruction, ATA_PROGRAM};
+use crate::instructions::guard::{
+    check_program_id, invoke_guarded, invoke_guarded_with_limits, Balance, BalanceLimit,
+};
+use crate::instructions::slot::check_deadline;
 
 const PUMP_PROGRAM: Pubkey = pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
 const PUMP_AMM_PROGRAM_ID: Pubkey = pubkey!("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+const PUMP_FEE_PROGRAM: Pubkey = pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
+const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+const MPL_TOKEN_METADATA_PROGRAM: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
 const ARG_LEN: usize = 24;
 
-fn to_account_metas(accounts: &[AccountInfo]) -> Vec<AccountMeta> {
-    let mut metas = Vec::with_capacity(accounts.len());
-    metas.append(
-        &mut accounts
-            .iter()
-            .map(|acc| match acc.is_writable {
-                false => AccountMeta::new_readonly(*acc.key, acc.is_signer),
-                true => AccountMeta::new(*acc.key, acc.is_signer),
-            })
-            .collect(),
-    );
-    metas
-}
-
-pub fn process_pump_buy(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
+// 旧版布局（没有 creator vault、交易量累加器和 fee_config）的账户数量，只用于识别过期布局
+const PUMP_LEGACY_ACCOUNTS_LEN: usize = 12;
+const PUMP_AMM_LEGACY_ACCOUNTS_LEN: usize = 17;
+
+const PUMP_BUY_ACCOUNTS_LEN: usize = 16;
+const PUMP_SELL_ACCOUNTS_LEN: usize = 14;
+const PUMP_AMM_BUY_ACCOUNTS_LEN: usize = 23;
+const PUMP_AMM_SELL_ACCOUNTS_LEN: usize = 21;
+
+// 内盘: global / mint / bonding_curve / associated_user / user
+const PUMP_GLOBAL_INDEX: usize = 0;
+const PUMP_MINT_INDEX: usize = 2;
+const PUMP_BONDING_CURVE_INDEX: usize = 3;
+const PUMP_USER_TOKEN_INDEX: usize = 5;
+const PUMP_USER_INDEX: usize = 6;
+// 外盘: pool / user / base_mint / quote_mint / user_base_token_account / user_quote_token_account /
+// quote_token_program / coin_creator_vault_ata / coin_creator_vault_authority
+const PUMP_AMM_POOL_INDEX: usize = 0;
+const PUMP_AMM_USER_INDEX: usize = 1;
+const PUMP_AMM_BASE_MINT_INDEX: usize = 3;
+const PUMP_AMM_QUOTE_MINT_INDEX: usize = 4;
+const PUMP_AMM_USER_BASE_INDEX: usize = 5;
+const PUMP_AMM_USER_QUOTE_INDEX: usize = 6;
+const PUMP_AMM_QUOTE_TOKEN_PROGRAM_INDEX: usize = 12;
+const PUMP_AMM_COIN_CREATOR_VAULT_ATA_INDEX: usize = 17;
+const PUMP_AMM_COIN_CREATOR_VAULT_AUTHORITY_INDEX: usize = 18;
+// 流动性: lp_mint / user_base_token_account / user_quote_token_account / user_pool_token_account
+const PUMP_AMM_LP_MINT_INDEX: usize = 5;
+const PUMP_AMM_LIQUIDITY_USER_BASE_INDEX: usize = 6;
+const PUMP_AMM_LIQUIDITY_USER_QUOTE_INDEX: usize = 7;
+const PUMP_AMM_LIQUIDITY_USER_LP_INDEX: usize = 8;
+
+const PUMP_LAUNCH_ACCOUNTS_LEN: usize = 21;
+const PUMP_AMM_LIQUIDITY_ACCOUNTS_LEN: usize = 15;
+const PUMP_CREATE_ACCOUNTS_LEN: usize = 14;
+
+// global, fee_recipient, mint, bonding_curve, associated_bonding_curve, associated_user, user,
+// system_program, token_program, creator_vault, event_authority, program,
+// global_volume_accumulator, user_volume_accumulator, fee_config, fee_program
+const PUMP_BUY_ACCOUNTS: [AccountRole; PUMP_BUY_ACCOUNTS_LEN] =
+    [R, W, R, W, W, W, WS, R, R, W, R, R, W, W, R, R];
+
+// global, fee_recipient, mint, bonding_curve, associated_bonding_curve, associated_user, user,
+// system_program, creator_vault, token_program, event_authority, program, fee_config, fee_program
+const PUMP_SELL_ACCOUNTS: [AccountRole; PUMP_SELL_ACCOUNTS_LEN] =
+    [R, W, R, W, W, W, WS, R, W, R, R, R, R, R];
+
+// pool, user, global_config, base_mint, quote_mint, user_base_token_account, user_quote_token_account,
+// pool_base_token_account, pool_quote_token_account, protocol_fee_recipient,
+// protocol_fee_recipient_token_account, base_token_program, quote_token_program, system_program,
+// associated_token_program, event_authority, program, coin_creator_vault_ata,
+// coin_creator_vault_authority, global_volume_accumulator, user_volume_accumulator, fee_config,
+// fee_program
+const PUMP_AMM_BUY_ACCOUNTS: [AccountRole; PUMP_AMM_BUY_ACCOUNTS_LEN] = [
+    W, WS, R, R, R, W, W, W, W, R, W, R, R, R, R, R, R, W, R, W, W, R, R,
+];
+
+// 与买入相同，但没有两个交易量累加器：..., coin_creator_vault_authority, fee_config, fee_program
+const PUMP_AMM_SELL_ACCOUNTS: [AccountRole; PUMP_AMM_SELL_ACCOUNTS_LEN] = [
+    W, WS, R, R, R, W, W, W, W, R, W, R, R, R, R, R, R, W, R, R, R,
+];
+
+// create 的 14 个账户：mint, mint_authority, bonding_curve, associated_bonding_curve, global,
+// mpl_token_metadata, metadata, user, system_program, token_program, associated_token_program,
+// rent, event_authority, program；之后为首笔买入额外需要的 fee_recipient, associated_user,
+// creator_vault, global_volume_accumulator, user_volume_accumulator, fee_config, fee_program
+const PUMP_LAUNCH_ACCOUNTS: [AccountRole; PUMP_LAUNCH_ACCOUNTS_LEN] = [
+    WS, R, W, W, R, R, W, WS, R, R, R, R, R, R, W, W, W, W, W, R, R,
+];
+
+// pool, global_config, user, base_mint, quote_mint, lp_mint, user_base_token_account,
+// user_quote_token_account, user_pool_token_account, pool_base_token_account,
+// pool_quote_token_account, token_program, token_2022_program, event_authority, program
+const PUMP_AMM_LIQUIDITY_ACCOUNTS: [AccountRole; PUMP_AMM_LIQUIDITY_ACCOUNTS_LEN] =
+    [W, R, WS, R, R, W, W, W, W, W, W, R, R, R, R];
+
+// 按内盘买入的账户顺序从发币账户中取出
+const PUMP_LAUNCH_BUY_INDEXES: [usize; PUMP_BUY_ACCOUNTS_LEN] =
+    [4, 14, 0, 2, 3, 15, 7, 8, 9, 16, 12, 13, 17, 18, 19, 20];
+
+/// 内盘新版布局中需要校验的派生账户位置
+struct CurveLayout {
+    discriminator: &'static [u8; 8],
+    schema: &'static [AccountRole],
+    creator_vault: usize,
+    /// global_volume_accumulator，其后紧跟 user_volume_accumulator（只有买入需要）
+    volume_accumulators: Option<usize>,
+    /// fee_config，其后紧跟 fee_program
+    fee_config: usize,
+}
+
+const PUMP_BUY_LAYOUT: CurveLayout = CurveLayout {
+    discriminator: &selector::pump::BUY,
+    schema: &PUMP_BUY_ACCOUNTS,
+    creator_vault: 9,
+    volume_accumulators: Some(12),
+    fee_config: 14,
+};
+
+const PUMP_SELL_LAYOUT: CurveLayout = CurveLayout {
+    discriminator: &selector::pump::SELL,
+    schema: &PUMP_SELL_ACCOUNTS,
+    creator_vault: 8,
+    volume_accumulators: None,
+    fee_config: 12,
+};
+
+/// 外盘新版布局中需要校验的派生账户位置，coin creator vault 两个账户的位置买卖相同
+struct AmmLayout {
+    schema: &'static [AccountRole],
+    volume_accumulators: Option<usize>,
+    fee_config: usize,
+}
+
+const PUMP_AMM_BUY_LAYOUT: AmmLayout = AmmLayout {
+    schema: &PUMP_AMM_BUY_ACCOUNTS,
+    volume_accumulators: Some(19),
+    fee_config: 21,
+};
+
+const PUMP_AMM_SELL_LAYOUT: AmmLayout = AmmLayout {
+    schema: &PUMP_AMM_SELL_ACCOUNTS,
+    volume_accumulators: None,
+    fee_config: 19,
+};
+
+// BondingCurve：8 字节鉴别器之后依次为 virtual_token_reserves, virtual_sol_reserves,
+// real_token_reserves, real_sol_reserves, token_total_supply (u64), complete (bool), creator (Pubkey)
+const CURVE_VIRTUAL_TOKEN_RESERVES_OFFSET: usize = 8;
+const CURVE_VIRTUAL_SOL_RESERVES_OFFSET: usize = 16;
+const CURVE_REAL_TOKEN_RESERVES_OFFSET: usize = 24;
+const CURVE_COMPLETE_OFFSET: usize = 48;
+const CURVE_CREATOR_OFFSET: usize = 49;
+// Global 中的 fee_basis_points / creator_fee_basis_points
+const GLOBAL_FEE_BASIS_POINTS_OFFSET: usize = 105;
+const GLOBAL_CREATOR_FEE_BASIS_POINTS_OFFSET: usize = 154;
+// PumpAMM Pool 中的 coin_creator
+const POOL_COIN_CREATOR_OFFSET: usize = 211;
+
+const BASIS_POINTS: u128 = 10_000;
+// Pump 的曲线价格 +1 与两项手续费均向上取整，调用方的 sol_margin 至少按这个值预留
+const PUMP_ROUNDING_MARGIN: u64 = 3;
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    let bytes = data
+        .get(offset..offset + 8)
+        .ok_or(ProxyError::InvalidAccountData)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey, ProgramError> {
+    let bytes = data
+        .get(offset..offset + 32)
+        .ok_or(ProxyError::InvalidAccountData)?;
+    Ok(Pubkey::new_from_array(bytes.try_into().unwrap()))
+}
+
+/// 执行时读取的联合曲线状态
+struct BondingCurve {
+    virtual_token_reserves: u64,
+    virtual_sol_reserves: u64,
+    real_token_reserves: u64,
+    /// 曲线已完成，代币已迁移到 PumpAMM
+    complete: bool,
+    /// 未设置时为默认公钥，此时不收取创作者手续费
+    creator: Pubkey,
+}
+
+impl BondingCurve {
+    fn load(account: &AccountInfo) -> Result<Self, ProgramError> {
+        if account.owner != &PUMP_PROGRAM {
+            return Err(ProxyError::InvalidAccountData.into());
+        }
+        let data = account.try_borrow_data()?;
+        Ok(Self {
+            virtual_token_reserves: read_u64(&data, CURVE_VIRTUAL_TOKEN_RESERVES_OFFSET)?,
+            virtual_sol_reserves: read_u64(&data, CURVE_VIRTUAL_SOL_RESERVES_OFFSET)?,
+            real_token_reserves: read_u64(&data, CURVE_REAL_TOKEN_RESERVES_OFFSET)?,
+            complete: *data
+                .get(CURVE_COMPLETE_OFFSET)
+                .ok_or(ProxyError::InvalidAccountData)?
+                != 0,
+            creator: read_pubkey(&data, CURVE_CREATOR_OFFSET).unwrap_or_default(),
+        })
+    }
+
+    /// 花费 `sol_amount`（含手续费）能买到的代币数量，与 Pump SDK 的算法一致；
+    /// `margin` lamports 不参与计算
+    fn buy_token_amount(&self, sol_amount: u64, fee_basis_points: u64, margin: u64) -> u64 {
+        let input = sol_amount.saturating_sub(margin) as u128 * BASIS_POINTS
+            / (BASIS_POINTS + fee_basis_points as u128);
+        let denominator = self.virtual_sol_reserves as u128 + input;
+        if denominator == 0 {
+            return 0;
+        }
+        let tokens = input * self.virtual_token_reserves as u128 / denominator;
+        tokens.min(self.real_token_reserves as u128) as u64
+    }
+}
+
+/// 买入时收取的总手续费（协议 + 创作者）
+fn buy_fee_basis_points(global: &AccountInfo, curve: &BondingCurve) -> Result<u64, ProgramError> {
+    if global.owner != &PUMP_PROGRAM {
+        return Err(ProxyError::InvalidAccountData.into());
+    }
+    let data = global.try_borrow_data()?;
+    let fee = read_u64(&data, GLOBAL_FEE_BASIS_POINTS_OFFSET)?;
+    let creator_fee = if curve.creator != Pubkey::default() {
+        read_u64(&data, GLOBAL_CREATOR_FEE_BASIS_POINTS_OFFSET)?
+    } else {
+        0
+    };
+    Ok(fee.saturating_add(creator_fee))
+}
+
+/// 外盘池子的 coin_creator，用于推导 coin creator vault
+fn pool_coin_creator(pool: &AccountInfo) -> Result<Pubkey, ProgramError> {
+    if pool.owner != &PUMP_AMM_PROGRAM_ID {
+        return Err(ProxyError::InvalidAccountData.into());
+    }
+    read_pubkey(&pool.try_borrow_data()?, POOL_COIN_CREATOR_OFFSET)
+}
+
+/// 账户数量不足新版布局时，能容纳旧版布局的判为过期布局，其余为账户不足
+fn check_layout(accounts: &[AccountInfo], legacy_len: usize, required: usize) -> ProgramResult {
+    if accounts.len() >= required {
+        return Ok(());
+    }
+    if accounts.len() >= legacy_len {
+        return Err(ProxyError::StaleAccountLayout.into());
+    }
+    Err(ProxyError::NotEnoughAccountKeys.into())
+}
+
+fn check_volume_accumulators(
+    accounts: &[AccountInfo],
+    index: usize,
+    user: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    check_derived_address(
+        &accounts[index],
+        &[b"global_volume_accumulator"],
+        program_id,
+    )?;
+    check_derived_address(
+        &accounts[index + 1],
+        &[b"user_volume_accumulator", user.key.as_ref()],
+        program_id,
+    )
+}
+
+fn check_fee_accounts(
+    accounts: &[AccountInfo],
+    index: usize,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    check_program_id(&accounts[index + 1], &[PUMP_FEE_PROGRAM])?;
+    check_derived_address(
+        &accounts[index],
+        &[b"fee_config", program_id.as_ref()],
+        &PUMP_FEE_PROGRAM,
+    )
+}
+
+fn pump_instruction(
+    program_id: Pubkey,
+    discriminator: &[u8; 8],
+    amount: u64,
+    limit: u64,
+    accounts: &[AccountInfo],
+    schema: &[AccountRole],
+    required: usize,
+) -> Result<Instruction, ProgramError> {
+    let metas = to_account_metas(accounts, schema, required)?;
+
     let mut data = Vec::with_capacity(ARG_LEN);
-    data.extend_from_slice(PUMPFUN_BUY_SELECTOR);
-    data.extend_from_slice(instruction_data);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&limit.to_le_bytes());
 
-    invoke_unchecked(
-        &Instruction {
-            program_id: PUMP_PROGRAM,
-            accounts: to_account_metas(accounts),
-            data,
-        },
+    Ok(Instruction {
+        program_id,
+        accounts: metas,
+        data,
+    })
+}
+
+// 校验内盘新版布局的 creator_vault、交易量累加器与 fee_config 后构造 CPI 指令
+fn curve_instruction(
+    accounts: &[AccountInfo],
+    layout: &CurveLayout,
+    amount: u64,
+    limit: u64,
+) -> Result<Instruction, ProgramError> {
+    check_layout(accounts, PUMP_LEGACY_ACCOUNTS_LEN, layout.schema.len())?;
+
+    let curve = BondingCurve::load(&accounts[PUMP_BONDING_CURVE_INDEX])?;
+    check_derived_address(
+        &accounts[layout.creator_vault],
+        &[b"creator-vault", curve.creator.as_ref()],
+        &PUMP_PROGRAM,
+    )?;
+    if let Some(index) = layout.volume_accumulators {
+        check_volume_accumulators(accounts, index, &accounts[PUMP_USER_INDEX], &PUMP_PROGRAM)?;
+    }
+    check_fee_accounts(accounts, layout.fee_config, &PUMP_PROGRAM)?;
+
+    pump_instruction(
+        PUMP_PROGRAM,
+        layout.discriminator,
+        amount,
+        limit,
         accounts,
+        layout.schema,
+        layout.schema.len(),
     )
 }
 
-pub fn process_pump_amm_buy(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
-    let mut data = Vec::with_capacity(ARG_LEN);
-    data.extend_from_slice(PUMPAMM_BUY_SELECTOR);
-    data.extend_from_slice(instruction_data);
+// 校验外盘新版布局的 coin creator vault、交易量累加器与 fee_config 后构造 CPI 指令
+fn amm_instruction(
+    accounts: &[AccountInfo],
+    layout: &AmmLayout,
+    discriminator: &[u8; 8],
+    amount: u64,
+    limit: u64,
+) -> Result<Instruction, ProgramError> {
+    check_layout(accounts, PUMP_AMM_LEGACY_ACCOUNTS_LEN, layout.schema.len())?;
 
-    invoke_unchecked(
-        &Instruction {
-            program_id: PUMP_AMM_PROGRAM_ID,
-            accounts: to_account_metas(accounts),
-            data,
-        },
+    let coin_creator = pool_coin_creator(&accounts[PUMP_AMM_POOL_INDEX])?;
+    let vault_authority = &accounts[PUMP_AMM_COIN_CREATOR_VAULT_AUTHORITY_INDEX];
+    check_derived_address(
+        vault_authority,
+        &[b"creator_vault", coin_creator.as_ref()],
+        &PUMP_AMM_PROGRAM_ID,
+    )?;
+    check_derived_address(
+        &accounts[PUMP_AMM_COIN_CREATOR_VAULT_ATA_INDEX],
+        &[
+            vault_authority.key.as_ref(),
+            accounts[PUMP_AMM_QUOTE_TOKEN_PROGRAM_INDEX].key.as_ref(),
+            accounts[PUMP_AMM_QUOTE_MINT_INDEX].key.as_ref(),
+        ],
+        &ATA_PROGRAM,
+    )?;
+    if let Some(index) = layout.volume_accumulators {
+        check_volume_accumulators(
+            accounts,
+            index,
+            &accounts[PUMP_AMM_USER_INDEX],
+            &PUMP_AMM_PROGRAM_ID,
+        )?;
+    }
+    check_fee_accounts(accounts, layout.fee_config, &PUMP_AMM_PROGRAM_ID)?;
+
+    pump_instruction(
+        PUMP_AMM_PROGRAM_ID,
+        discriminator,
+        amount,
+        limit,
         accounts,
+        layout.schema,
+        layout.schema.len(),
     )
 }
 
-pub fn process_pump_sell(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
-    let mut data = Vec::with_capacity(ARG_LEN);
-    data.extend_from_slice(PUMPFUN_SELL_SELECTOR);
-    data.extend_from_slice(instruction_data);
+pub fn process_pump_buy(accounts: &[AccountInfo], args: &PumpBuyArgs) -> ProgramResult {
+    let instruction = curve_instruction(
+        accounts,
+        &PUMP_BUY_LAYOUT,
+        args.token_amount,
+        args.max_sol_cost,
+    )?;
 
-    invoke_unchecked(
-        &Instruction {
-            program_id: PUMP_PROGRAM,
-            accounts: to_account_metas(accounts),
-            data,
-        },
+    invoke_guarded(
+        &args.guard,
+        Balance::Lamports(&accounts[PUMP_USER_INDEX]),
+        Balance::Token(&accounts[PUMP_USER_TOKEN_INDEX]),
+        &instruction,
         accounts,
     )
 }
 
-pub fn process_pump_amm_sell(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
-    let mut data = Vec::with_capacity(ARG_LEN);
-    data.extend_from_slice(PUMPAMM_SELL_SELECTOR);
-    data.extend_from_slice(instruction_data);
+// 账户与 `process_pump_buy` 相同；代币数量按执行时的储备与 Global 手续费计算，
+// `max_sol_cost` 直接取 `sol_amount`
+pub fn process_pump_buy_exact_sol(
+    accounts: &[AccountInfo],
+    args: &PumpBuyExactSolArgs,
+) -> ProgramResult {
+    check_layout(accounts, PUMP_LEGACY_ACCOUNTS_LEN, PUMP_BUY_ACCOUNTS_LEN)?;
+
+    let curve = BondingCurve::load(&accounts[PUMP_BONDING_CURVE_INDEX])?;
+    let fee_basis_points = buy_fee_basis_points(&accounts[PUMP_GLOBAL_INDEX], &curve)?;
+    let margin = args.sol_margin.max(PUMP_ROUNDING_MARGIN);
+    let token_amount = curve.buy_token_amount(args.sol_amount, fee_basis_points, margin);
+    if token_amount == 0 || token_amount < args.min_token_amount {
+        return Err(ProxyError::SlippageExceeded.into());
+    }
+
+    let instruction = curve_instruction(accounts, &PUMP_BUY_LAYOUT, token_amount, args.sol_amount)?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Lamports(&accounts[PUMP_USER_INDEX]),
+        Balance::Token(&accounts[PUMP_USER_TOKEN_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+/// 该 mint 迁移后的 PumpAMM 标准池：creator 为内盘的 pool-authority PDA，index 为 0，报价币为 WSOL
+fn check_canonical_pool(pool: &AccountInfo, mint: &Pubkey) -> ProgramResult {
+    let (pool_authority, _) =
+        Pubkey::find_program_address(&[b"pool-authority", mint.as_ref()], &PUMP_PROGRAM);
+    check_derived_address(
+        pool,
+        &[
+            b"pool",
+            &0u16.to_le_bytes(),
+            pool_authority.as_ref(),
+            mint.as_ref(),
+            WSOL_MINT.as_ref(),
+        ],
+        &PUMP_AMM_PROGRAM_ID,
+    )
+}
+
+// 账户顺序：与 is_buy 对应的内盘买入或卖出账户，之后为同方向 PumpAMM 的账户。
+// 曲线未完成时只使用内盘账户，后面的 PumpAMM 账户可以省略
+pub fn process_pump_trade(accounts: &[AccountInfo], args: &PumpTradeArgs) -> ProgramResult {
+    let (curve_layout, amm_layout, amm_discriminator) = if args.is_buy {
+        (
+            &PUMP_BUY_LAYOUT,
+            &PUMP_AMM_BUY_LAYOUT,
+            &selector::pump_amm::BUY,
+        )
+    } else {
+        (
+            &PUMP_SELL_LAYOUT,
+            &PUMP_AMM_SELL_LAYOUT,
+            &selector::pump_amm::SELL,
+        )
+    };
+    check_layout(
+        accounts,
+        PUMP_LEGACY_ACCOUNTS_LEN,
+        curve_layout.schema.len(),
+    )?;
+    let (curve_accounts, amm_accounts) = accounts.split_at(curve_layout.schema.len());
+
+    // 已迁移时不会调用 Pump，complete 标志必须来自该 mint 自己的联合曲线
+    let mint = curve_accounts[PUMP_MINT_INDEX].key;
+    let bonding_curve = &curve_accounts[PUMP_BONDING_CURVE_INDEX];
+    check_derived_address(
+        bonding_curve,
+        &[b"bonding-curve", mint.as_ref()],
+        &PUMP_PROGRAM,
+    )?;
+
+    let curve = BondingCurve::load(bonding_curve)?;
+    if !curve.complete {
+        let instruction = curve_instruction(
+            curve_accounts,
+            curve_layout,
+            args.token_amount,
+            args.sol_limit,
+        )?;
+        let user_token = &curve_accounts[PUMP_USER_TOKEN_INDEX];
+        let user = &curve_accounts[PUMP_USER_INDEX];
+        let (source, destination) = if args.is_buy {
+            (Balance::Lamports(user), Balance::Token(user_token))
+        } else {
+            (Balance::Token(user_token), Balance::Lamports(user))
+        };
+        return invoke_guarded(&args.guard, source, destination, &instruction, accounts);
+    }
+
+    let instruction = amm_instruction(
+        amm_accounts,
+        amm_layout,
+        amm_discriminator,
+        args.token_amount,
+        args.sol_limit,
+    )?;
+
+    if amm_accounts[PUMP_AMM_BASE_MINT_INDEX].key != mint {
+        return Err(ProxyError::InvalidDerivedAccount.into());
+    }
+    check_canonical_pool(&amm_accounts[PUMP_AMM_POOL_INDEX], mint)?;
+
+    let base = &amm_accounts[PUMP_AMM_USER_BASE_INDEX];
+    let quote = &amm_accounts[PUMP_AMM_USER_QUOTE_INDEX];
+    let (source, destination) = if args.is_buy {
+        (Balance::Token(quote), Balance::Token(base))
+    } else {
+        (Balance::Token(base), Balance::Token(quote))
+    };
+    invoke_guarded(&args.guard, source, destination, &instruction, accounts)
+}
+
+pub fn process_pump_amm_buy(accounts: &[AccountInfo], args: &PumpAmmBuyArgs) -> ProgramResult {
+    let instruction = amm_instruction(
+        accounts,
+        &PUMP_AMM_BUY_LAYOUT,
+        &selector::pump_amm::BUY,
+        args.base_amount_out,
+        args.max_quote_amount_in,
+    )?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&accounts[PUMP_AMM_USER_QUOTE_INDEX]),
+        Balance::Token(&accounts[PUMP_AMM_USER_BASE_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+// 固定花费的报价币数量，由 PumpAMM 按池子储备计算买到的代币数量
+pub fn process_pump_amm_buy_exact_quote_in(
+    accounts: &[AccountInfo],
+    args: &PumpAmmBuyExactQuoteInArgs,
+) -> ProgramResult {
+    let mut instruction = amm_instruction(
+        accounts,
+        &PUMP_AMM_BUY_LAYOUT,
+        &selector::pump_amm::BUY_EXACT_QUOTE_IN,
+        args.spendable_quote_in,
+        args.min_base_amount_out,
+    )?;
+    // track_volume: OptionBool
+    instruction.data.push(args.track_volume as u8);
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&accounts[PUMP_AMM_USER_QUOTE_INDEX]),
+        Balance::Token(&accounts[PUMP_AMM_USER_BASE_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+pub fn process_pump_sell(accounts: &[AccountInfo], args: &PumpSellArgs) -> ProgramResult {
+    let instruction = curve_instruction(
+        accounts,
+        &PUMP_SELL_LAYOUT,
+        args.token_amount,
+        args.min_sol_output,
+    )?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&accounts[PUMP_USER_TOKEN_INDEX]),
+        Balance::Lamports(&accounts[PUMP_USER_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+pub fn process_pump_amm_sell(accounts: &[AccountInfo], args: &PumpAmmSellArgs) -> ProgramResult {
+    let instruction = amm_instruction(
+        accounts,
+        &PUMP_AMM_SELL_LAYOUT,
+        &selector::pump_amm::SELL,
+        args.base_amount_in,
+        args.min_quote_amount_out,
+    )?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&accounts[PUMP_AMM_USER_BASE_INDEX]),
+        Balance::Token(&accounts[PUMP_AMM_USER_QUOTE_INDEX]),
+        &instruction,
+        accounts,
+    )
+}
+
+// deposit / withdraw 共用同一组账户；池子须属于 PumpAMM，lp_mint 须为该池子的派生地址
+fn liquidity_instruction(
+    accounts: &[AccountInfo],
+    discriminator: &[u8; 8],
+    lp_amount: u64,
+    base_limit: u64,
+    quote_limit: u64,
+) -> Result<Instruction, ProgramError> {
+    let mut instruction = pump_instruction(
+        PUMP_AMM_PROGRAM_ID,
+        discriminator,
+        lp_amount,
+        base_limit,
+        accounts,
+        &PUMP_AMM_LIQUIDITY_ACCOUNTS,
+        PUMP_AMM_LIQUIDITY_ACCOUNTS_LEN,
+    )?;
+    instruction
+        .data
+        .extend_from_slice(&quote_limit.to_le_bytes());
+
+    let pool = &accounts[PUMP_AMM_POOL_INDEX];
+    if pool.owner != &PUMP_AMM_PROGRAM_ID {
+        return Err(ProxyError::InvalidAccountData.into());
+    }
+    check_derived_address(
+        &accounts[PUMP_AMM_LP_MINT_INDEX],
+        &[b"pool_lp_mint", pool.key.as_ref()],
+        &PUMP_AMM_PROGRAM_ID,
+    )?;
+
+    Ok(instruction)
+}
+
+// `guard` 校验报价币账户的实际支出与 LP 代币账户的实际到账，
+// 两侧的实际投入另按 `max_base_amount_in` / `max_quote_amount_in` 校验
+pub fn process_pump_amm_deposit(
+    accounts: &[AccountInfo],
+    args: &PumpAmmDepositArgs,
+) -> ProgramResult {
+    let instruction = liquidity_instruction(
+        accounts,
+        &selector::pump_amm::DEPOSIT,
+        args.lp_token_amount_out,
+        args.max_base_amount_in,
+        args.max_quote_amount_in,
+    )?;
+
+    let base = Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_BASE_INDEX]);
+    let quote = Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_QUOTE_INDEX]);
+    invoke_guarded_with_limits(
+        &args.guard,
+        quote,
+        Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_LP_INDEX]),
+        &[
+            BalanceLimit::MaxDecrease(base, args.max_base_amount_in),
+            BalanceLimit::MaxDecrease(quote, args.max_quote_amount_in),
+        ],
+        &instruction,
+        accounts,
+    )
+}
+
+// `guard` 校验 LP 代币账户的实际支出与报价币账户的实际到账，
+// 两侧的实际到账另按 `min_base_amount_out` / `min_quote_amount_out` 校验
+pub fn process_pump_amm_withdraw(
+    accounts: &[AccountInfo],
+    args: &PumpAmmWithdrawArgs,
+) -> ProgramResult {
+    let instruction = liquidity_instruction(
+        accounts,
+        &selector::pump_amm::WITHDRAW,
+        args.lp_token_amount_in,
+        args.min_base_amount_out,
+        args.min_quote_amount_out,
+    )?;
+
+    let base = Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_BASE_INDEX]);
+    let quote = Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_QUOTE_INDEX]);
+    invoke_guarded_with_limits(
+        &args.guard,
+        Balance::Token(&accounts[PUMP_AMM_LIQUIDITY_USER_LP_INDEX]),
+        quote,
+        &[
+            BalanceLimit::MinIncrease(base, args.min_base_amount_out),
+            BalanceLimit::MinIncrease(quote, args.min_quote_amount_out),
+        ],
+        &instruction,
+        accounts,
+    )
+}
+
+// 同一条指令内完成 create → 创建用户 ATA → 首笔买入，三步原子执行。
+// CPI 前先校验 mint_authority、bonding_curve、associated_bonding_curve、metadata 均为对应的派生地址，
+// 并在 create 之前检查截止条件，过期时不会先创建代币
+pub fn process_pump_launch(accounts: &[AccountInfo], args: &PumpLaunchArgs) -> ProgramResult {
+    let metas = to_account_metas(accounts, &PUMP_LAUNCH_ACCOUNTS, PUMP_LAUNCH_ACCOUNTS_LEN)?;
+
+    let [mint, mint_authority, bonding_curve, associated_bonding_curve, _global, _mpl_token_metadata, metadata, user, system_program, token_program, _associated_token_program, _rent, _event_authority, _program, _fee_recipient, associated_user, _creator_vault, _global_volume_accumulator, _user_volume_accumulator, _fee_config, _fee_program] =
+        array_ref![accounts, 0, PUMP_LAUNCH_ACCOUNTS_LEN];
+
+    check_derived_address(mint_authority, &[b"mint-authority"], &PUMP_PROGRAM)?;
+    check_derived_address(
+        bonding_curve,
+        &[b"bonding-curve", mint.key.as_ref()],
+        &PUMP_PROGRAM,
+    )?;
+    check_derived_address(
+        associated_bonding_curve,
+        &[
+            bonding_curve.key.as_ref(),
+            token_program.key.as_ref(),
+            mint.key.as_ref(),
+        ],
+        &ATA_PROGRAM,
+    )?;
+    check_derived_address(
+        metadata,
+        &[
+            b"metadata",
+            MPL_TOKEN_METADATA_PROGRAM.as_ref(),
+            mint.key.as_ref(),
+        ],
+        &MPL_TOKEN_METADATA_PROGRAM,
+    )?;
+
+    check_deadline(&args.guard.deadline)?;
+
+    let mut data =
+        Vec::with_capacity(8 + 12 + args.name.len() + args.symbol.len() + args.uri.len() + 32);
+    data.extend_from_slice(&selector::pump::CREATE);
+    pack_string(&args.name, &mut data);
+    pack_string(&args.symbol, &mut data);
+    pack_string(&args.uri, &mut data);
+    data.extend_from_slice(&args.creator);
 
     invoke_unchecked(
         &Instruction {
-            program_id: PUMP_AMM_PROGRAM_ID,
-            accounts: to_account_metas(accounts),
+            program_id: PUMP_PROGRAM,
+            accounts: metas[..PUMP_CREATE_ACCOUNTS_LEN].to_vec(),
             data,
         },
         accounts,
+    )?;
+
+    invoke_unchecked(
+        &create_associated_token_account_instruction(
+            user.key,
+            associated_user.key,
+            mint.key,
+            system_program.key,
+            token_program.key,
+            true,
+        ),
+        accounts,
+    )?;
+
+    // 曲线在 create 之后才存在，creator_vault 等派生账户在这里按新建的曲线校验
+    let buy_accounts: Vec<AccountInfo> = PUMP_LAUNCH_BUY_INDEXES
+        .iter()
+        .map(|&i| accounts[i].clone())
+        .collect();
+    let instruction = curve_instruction(
+        &buy_accounts,
+        &PUMP_BUY_LAYOUT,
+        args.token_amount,
+        args.max_sol_cost,
+    )?;
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Lamports(user),
+        Balance::Token(associated_user),
+        &instruction,
+        accounts,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(virtual_sol_reserves: u64, real_token_reserves: u64) -> BondingCurve {
+        BondingCurve {
+            virtual_token_reserves: 1_000_000,
+            virtual_sol_reserves,
+            real_token_reserves,
+            complete: false,
+            creator: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn buy_token_amount_without_fee() {
+        // 预留 3 lamports 后投入 1_000，储备 1_000 lamports 时买到一半虚拟代币
+        assert_eq!(
+            curve(1_000, u64::MAX).buy_token_amount(1_003, 0, 3),
+            500_000
+        );
+    }
+
+    #[test]
+    fn buy_token_amount_deducts_fee() {
+        // 10_100 lamports 中 1% 为手续费
+        assert_eq!(
+            curve(10_000, u64::MAX).buy_token_amount(10_103, 100, 3),
+            500_000
+        );
+    }
+
+    #[test]
+    fn buy_token_amount_zero_denominator() {
+        assert_eq!(curve(0, u64::MAX).buy_token_amount(3, 0, 3), 0);
+    }
+
+    #[test]
+    fn buy_token_amount_capped_by_real_reserves() {
+        assert_eq!(curve(1_000, 100).buy_token_amount(1_003, 0, 3), 100);
+    }
+
+    #[test]
+    fn buy_token_amount_sol_below_margin() {
+        assert_eq!(curve(1_000, u64::MAX).buy_token_amount(2, 0, 3), 0);
+    }
+
+    // Pump 程序买入 `tokens` 个代币时实际收取的 lamports（含手续费）
+    fn pump_buy_cost(curve: &BondingCurve, tokens: u64, fee_basis_points: u64) -> u64 {
+        let tokens = tokens as u128;
+        let sol_cost = (tokens * curve.virtual_sol_reserves as u128)
+            .div_ceil(curve.virtual_token_reserves as u128 - tokens)
+            + 1;
+        let fee = (sol_cost * fee_basis_points as u128).div_ceil(BASIS_POINTS);
+        (sol_cost + fee) as u64
+    }
+
+    #[test]
+    fn sol_margin_covers_fee_config_gap() {
+        let curve = BondingCurve {
+            virtual_token_reserves: 1_073_000_000_000_000,
+            virtual_sol_reserves: 30_000_000_000,
+            real_token_reserves: u64::MAX,
+            complete: false,
+            creator: Pubkey::default(),
+        };
+        let sol_amount = 1_000_000_000;
+        // Global 为 1%，fee_config 实际收取 1.25%
+        let (global_bps, fee_config_bps) = (100, 125);
+
+        // 默认余量下 Pump 的实际花费超过 sol_amount
+        let tokens = curve.buy_token_amount(sol_amount, global_bps, PUMP_ROUNDING_MARGIN);
+        assert!(pump_buy_cost(&curve, tokens, fee_config_bps) > sol_amount);
+
+        // 按费率差设置的余量能让实际花费不超过 sol_amount
+        let margin = sol_amount * (fee_config_bps - global_bps) / 10_000 + PUMP_ROUNDING_MARGIN;
+        let tokens = curve.buy_token_amount(sol_amount, global_bps, margin);
+        assert!(tokens > 0);
+        assert!(pump_buy_cost(&curve, tokens, fee_config_bps) <= sol_amount);
+    }
+}
@@ -1,21 +1,59 @@
+use amm_proxy_interface::{
+    instruction::{RaydiumSwapArgs, RaydiumSwapBaseInArgs, RaydiumSwapBaseOutArgs, SwapGuard},
+    selector::raydium_amm,
+};
 use arrayref::array_ref;
 use solana_program::{
     account_info::AccountInfo,
     entrypoint::ProgramResult,
     instruction::{AccountMeta, Instruction},
-    program::invoke_unchecked,
+    pubkey,
+    pubkey::Pubkey,
 };
 
-pub const RAYDIUM_BUY_SELECTOR: &[u8; 8] = &[182, 77, 232, 39, 117, 138, 183, 72];
-pub const RAYDIUM_SELL_SELECTOR: &[u8; 8] = &[183, 77, 232, 39, 117, 138, 183, 72];
+use crate::error::ProxyError;
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+#[cfg(not(feature = "devnet"))]
+const RAYDIUM_AMM_PROGRAMS: [Pubkey; 1] = [pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8")];
+#[cfg(feature = "devnet")]
+const RAYDIUM_AMM_PROGRAMS: [Pubkey; 1] = [pubkey!("HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8")];
+
+const RAYDIUM_ACCOUNTS_LEN: usize = 9;
+const RAYDIUM_MARKET_ACCOUNTS_LEN: usize = 19;
+// 指令标签 (u8) + amount (u64) + other_amount_threshold (u64)
+const RAYDIUM_DATA_LEN: usize = 17;
+
+fn check_accounts(accounts: &[AccountInfo], min_accounts: usize) -> ProgramResult {
+    if accounts.len() < min_accounts {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    }
+    Ok(())
+}
+
+fn swap_data(tag: u8, amount: u64, other_amount_threshold: u64) -> Vec<u8> {
+    let mut data = Vec::with_capacity(RAYDIUM_DATA_LEN);
+    data.push(tag);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&other_amount_threshold.to_le_bytes());
+    data
+}
+
+// OpenBook 相关的 10 个账户用 amm_id 占位，只适用于不访问市场的池子
+fn invoke_swap(accounts: &[AccountInfo], data: Vec<u8>, guard: &SwapGuard) -> ProgramResult {
+    check_accounts(accounts, RAYDIUM_ACCOUNTS_LEN)?;
 
-pub fn process_raydium_buy(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
     let [amm_program, token_program, amm_id, amm_authority, amm_coin_vault, amm_pc_vault, user_source_token, user_destination_token, user_source_owner] =
         array_ref![accounts, 0, 9];
 
+    check_program_id(amm_program, &RAYDIUM_AMM_PROGRAMS)?;
+
     let amm_pool = *amm_id.key;
 
-    invoke_unchecked(
+    invoke_guarded(
+        guard,
+        Balance::Token(user_source_token),
+        Balance::Token(user_destination_token),
         &Instruction {
             program_id: *amm_program.key,
             accounts: vec![
@@ -37,42 +75,113 @@ pub fn process_raydium_buy(accounts: &[AccountInfo], instruction_data: &[u8]) ->
                 AccountMeta::new(*user_destination_token.key, false),
                 AccountMeta::new_readonly(*user_source_owner.key, true),
             ],
-            data: instruction_data.to_vec(),
+            data,
         },
         accounts,
     )
 }
 
-pub fn process_raydium_sell(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
-    let [amm_program, token_program, amm_id, amm_authority, amm_coin_vault, amm_pc_vault, user_source_token, user_destination_token, user_source_owner] =
-        array_ref![accounts, 0, 9];
+// 传入真实的 open_orders / target_orders 与 OpenBook 市场账户，适用于仍校验这些账户的旧池子
+fn invoke_market_swap(accounts: &[AccountInfo], data: Vec<u8>, guard: &SwapGuard) -> ProgramResult {
+    check_accounts(accounts, RAYDIUM_MARKET_ACCOUNTS_LEN)?;
 
-    let amm_pool = *amm_id.key;
+    let [amm_program, token_program, amm_id, amm_authority, amm_open_orders, amm_target_orders, amm_coin_vault, amm_pc_vault, market_program, market, market_bids, market_asks, market_event_queue, market_coin_vault, market_pc_vault, market_vault_signer, user_source_token, user_destination_token, user_source_owner] =
+        array_ref![accounts, 0, 19];
+
+    check_program_id(amm_program, &RAYDIUM_AMM_PROGRAMS)?;
 
-    invoke_unchecked(
+    invoke_guarded(
+        guard,
+        Balance::Token(user_source_token),
+        Balance::Token(user_destination_token),
         &Instruction {
             program_id: *amm_program.key,
             accounts: vec![
                 AccountMeta::new_readonly(*token_program.key, false),
-                AccountMeta::new(amm_pool, false),
+                AccountMeta::new(*amm_id.key, false),
                 AccountMeta::new_readonly(*amm_authority.key, false),
-                AccountMeta::new(amm_pool, false),
+                AccountMeta::new(*amm_open_orders.key, false),
+                AccountMeta::new(*amm_target_orders.key, false),
                 AccountMeta::new(*amm_coin_vault.key, false),
                 AccountMeta::new(*amm_pc_vault.key, false),
-                AccountMeta::new_readonly(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new(amm_pool, false),
-                AccountMeta::new_readonly(amm_pool, false),
+                AccountMeta::new_readonly(*market_program.key, false),
+                AccountMeta::new(*market.key, false),
+                AccountMeta::new(*market_bids.key, false),
+                AccountMeta::new(*market_asks.key, false),
+                AccountMeta::new(*market_event_queue.key, false),
+                AccountMeta::new(*market_coin_vault.key, false),
+                AccountMeta::new(*market_pc_vault.key, false),
+                AccountMeta::new_readonly(*market_vault_signer.key, false),
                 AccountMeta::new(*user_source_token.key, false),
                 AccountMeta::new(*user_destination_token.key, false),
                 AccountMeta::new_readonly(*user_source_owner.key, true),
             ],
-            data: instruction_data.to_vec(),
+            data,
         },
         accounts,
     )
 }
+
+pub fn process_raydium_buy(accounts: &[AccountInfo], args: &RaydiumSwapArgs) -> ProgramResult {
+    let data = swap_data(args.instruction, args.amount, args.other_amount_threshold);
+
+    invoke_swap(accounts, data, &args.guard)
+}
+
+pub fn process_raydium_sell(accounts: &[AccountInfo], args: &RaydiumSwapArgs) -> ProgramResult {
+    let data = swap_data(args.instruction, args.amount, args.other_amount_threshold);
+
+    invoke_swap(accounts, data, &args.guard)
+}
+
+pub fn process_raydium_swap_base_in(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseInArgs,
+) -> ProgramResult {
+    let data = swap_data(
+        raydium_amm::SWAP_BASE_IN,
+        args.amount_in,
+        args.minimum_amount_out,
+    );
+
+    invoke_swap(accounts, data, &args.guard)
+}
+
+pub fn process_raydium_swap_base_out(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseOutArgs,
+) -> ProgramResult {
+    let data = swap_data(
+        raydium_amm::SWAP_BASE_OUT,
+        args.max_amount_in,
+        args.amount_out,
+    );
+
+    invoke_swap(accounts, data, &args.guard)
+}
+
+pub fn process_raydium_market_swap_base_in(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseInArgs,
+) -> ProgramResult {
+    let data = swap_data(
+        raydium_amm::SWAP_BASE_IN,
+        args.amount_in,
+        args.minimum_amount_out,
+    );
+
+    invoke_market_swap(accounts, data, &args.guard)
+}
+
+pub fn process_raydium_market_swap_base_out(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseOutArgs,
+) -> ProgramResult {
+    let data = swap_data(
+        raydium_amm::SWAP_BASE_OUT,
+        args.max_amount_in,
+        args.amount_out,
+    );
+
+    invoke_market_swap(accounts, data, &args.guard)
+}
@@ -0,0 +1,60 @@
+use amm_proxy_interface::{instruction::RaydiumClmmSwapArgs, selector::raydium_clmm};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas_with_remaining, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+#[cfg(not(feature = "devnet"))]
+const RAYDIUM_CLMM_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK")];
+#[cfg(feature = "devnet")]
+const RAYDIUM_CLMM_PROGRAMS: [Pubkey; 1] = [pubkey!("devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH")];
+
+// 鉴别器 + amount + other_amount_threshold + sqrt_price_limit_x64 + is_base_input
+const ARG_LEN: usize = 41;
+
+// payer, amm_config, pool_state, input_token_account, output_token_account, input_vault,
+// output_vault, observation_state, token_program, token_program_2022, memo_program,
+// input_vault_mint, output_vault_mint
+const CLMM_ACCOUNTS: [AccountRole; 13] = [S, R, W, W, W, W, W, W, R, R, R, R, R];
+
+const CLMM_INPUT_TOKEN_INDEX: usize = 3;
+const CLMM_OUTPUT_TOKEN_INDEX: usize = 4;
+
+// 账户顺序：clmm_program + swap_v2 的 13 个固定账户 + 可变数量的 tick array
+// （需要时 tickarray_bitmap_extension 放在 tick array 之前），至少一个 tick array
+pub fn process_raydium_clmm_swap(
+    accounts: &[AccountInfo],
+    args: &RaydiumClmmSwapArgs,
+) -> ProgramResult {
+    let Some((clmm_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(clmm_program, &RAYDIUM_CLMM_PROGRAMS)?;
+
+    let metas = to_account_metas_with_remaining(swap_accounts, &CLMM_ACCOUNTS, W, 1)?;
+
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(&raydium_clmm::SWAP_V2);
+    data.extend_from_slice(&args.amount.to_le_bytes());
+    data.extend_from_slice(&args.other_amount_threshold.to_le_bytes());
+    data.extend_from_slice(&args.sqrt_price_limit_x64.to_le_bytes());
+    data.push(args.is_base_input as u8);
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&swap_accounts[CLMM_INPUT_TOKEN_INDEX]),
+        Balance::Token(&swap_accounts[CLMM_OUTPUT_TOKEN_INDEX]),
+        &Instruction {
+            program_id: *clmm_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
@@ -0,0 +1,91 @@
+use amm_proxy_interface::{
+    instruction::{RaydiumSwapBaseInArgs, RaydiumSwapBaseOutArgs, SwapGuard},
+    selector::raydium_cpmm,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+#[cfg(not(feature = "devnet"))]
+const RAYDIUM_CPMM_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C")];
+#[cfg(feature = "devnet")]
+const RAYDIUM_CPMM_PROGRAMS: [Pubkey; 1] =
+    [pubkey!("DRaycpLY18LhpbydsBWbVJtxpNv9oXPgjRSfpF2bWpYb")];
+
+const ARG_LEN: usize = 24;
+
+const CPMM_ACCOUNTS_LEN: usize = 13;
+
+// payer, authority, amm_config, pool_state, input_token_account, output_token_account,
+// input_vault, output_vault, input_token_program, output_token_program,
+// input_token_mint, output_token_mint, observation_state
+const CPMM_ACCOUNTS: [AccountRole; CPMM_ACCOUNTS_LEN] = [S, R, R, W, W, W, W, W, R, R, R, R, W];
+
+const CPMM_INPUT_TOKEN_INDEX: usize = 4;
+const CPMM_OUTPUT_TOKEN_INDEX: usize = 5;
+
+// 账户顺序：cpmm_program + CPMM swap 的 13 个账户
+fn invoke_swap(
+    accounts: &[AccountInfo],
+    discriminator: &[u8; 8],
+    amount: u64,
+    limit: u64,
+    guard: &SwapGuard,
+) -> ProgramResult {
+    let Some((cpmm_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(cpmm_program, &RAYDIUM_CPMM_PROGRAMS)?;
+
+    let metas = to_account_metas(swap_accounts, &CPMM_ACCOUNTS, CPMM_ACCOUNTS_LEN)?;
+
+    let mut data = Vec::with_capacity(ARG_LEN);
+    data.extend_from_slice(discriminator);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.extend_from_slice(&limit.to_le_bytes());
+
+    invoke_guarded(
+        guard,
+        Balance::Token(&swap_accounts[CPMM_INPUT_TOKEN_INDEX]),
+        Balance::Token(&swap_accounts[CPMM_OUTPUT_TOKEN_INDEX]),
+        &Instruction {
+            program_id: *cpmm_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
+
+pub fn process_raydium_cpmm_swap_base_input(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseInArgs,
+) -> ProgramResult {
+    invoke_swap(
+        accounts,
+        &raydium_cpmm::SWAP_BASE_INPUT,
+        args.amount_in,
+        args.minimum_amount_out,
+        &args.guard,
+    )
+}
+
+pub fn process_raydium_cpmm_swap_base_output(
+    accounts: &[AccountInfo],
+    args: &RaydiumSwapBaseOutArgs,
+) -> ProgramResult {
+    invoke_swap(
+        accounts,
+        &raydium_cpmm::SWAP_BASE_OUTPUT,
+        args.max_amount_in,
+        args.amount_out,
+        &args.guard,
+    )
+}
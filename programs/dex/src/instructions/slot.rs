@@ -1,31 +1,103 @@
-use solana_program::{
-    clock::Clock, entrypoint::ProgramResult, program_error::ProgramError, sysvar::Sysvar,
-};
+use amm_proxy_interface::instruction::{Deadline, ExpiredSlotArgs, ValidityWindowArgs};
+use solana_program::{clock::Clock, entrypoint::ProgramResult, sysvar::Sysvar};
 
-pub const EXPIRED_SLOT_SELECTOR: &[u8; 8] = &[169, 134, 33, 62, 168, 2, 246, 176];
+use crate::error::ProxyError;
 
-#[derive(Debug, Clone)]
-pub enum MyError {
-    SlotExpired,
+pub fn process_expired_slot(args: &ExpiredSlotArgs) -> ProgramResult {
+    check_deadline(&Deadline {
+        slot: Some(args.expiry_slot),
+        unix_timestamp: None,
+    })
 }
 
-impl From<MyError> for ProgramError {
-    fn from(e: MyError) -> Self {
-        ProgramError::Custom(e as u32) // 自定义错误码更清晰
+/// 交换指令内联的截止条件，未设置时不读取 `Clock`
+pub fn check_deadline(deadline: &Deadline) -> ProgramResult {
+    if *deadline == Deadline::NONE {
+        return Ok(());
     }
+
+    let clock = Clock::get()?;
+
+    if matches!(deadline.slot, Some(expiry_slot) if clock.slot > expiry_slot) {
+        return Err(ProxyError::SlotExpired.into());
+    }
+    if matches!(deadline.unix_timestamp, Some(expiry) if clock.unix_timestamp > expiry) {
+        return Err(ProxyError::TimestampExpired.into());
+    }
+
+    Ok(())
 }
-pub fn process_expired_slot(instruction_data: &[u8]) -> ProgramResult {
-    let expiry_slot = u64::from_le_bytes(
-        instruction_data
-            .try_into()
-            .map_err(|_| ProgramError::InvalidInstructionData)?,
-    );
 
+/// 槽位与时间戳窗口，每个越界的边界返回各自的错误码
+pub fn process_validity_window(args: &ValidityWindowArgs) -> ProgramResult {
     let clock = Clock::get()?;
+    check_validity_window(args, clock.slot, clock.unix_timestamp)
+}
 
-    if clock.slot > expiry_slot {
-        return Err(MyError::SlotExpired.into());
+fn check_validity_window(
+    args: &ValidityWindowArgs,
+    slot: u64,
+    unix_timestamp: i64,
+) -> ProgramResult {
+    if slot < args.min_slot {
+        return Err(ProxyError::SlotNotReached.into());
+    }
+    if slot > args.max_slot {
+        return Err(ProxyError::SlotExpired.into());
+    }
+    if unix_timestamp < args.min_unix_timestamp {
+        return Err(ProxyError::TimestampNotReached.into());
+    }
+    if unix_timestamp > args.max_unix_timestamp {
+        return Err(ProxyError::TimestampExpired.into());
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: ValidityWindowArgs = ValidityWindowArgs {
+        min_slot: 100,
+        max_slot: 200,
+        min_unix_timestamp: 1_000,
+        max_unix_timestamp: 2_000,
+    };
+
+    #[test]
+    fn slot_bounds_are_inclusive() {
+        assert_eq!(check_validity_window(&WINDOW, 100, 1_500), Ok(()));
+        assert_eq!(check_validity_window(&WINDOW, 200, 1_500), Ok(()));
+        assert_eq!(
+            check_validity_window(&WINDOW, 99, 1_500),
+            Err(ProxyError::SlotNotReached.into())
+        );
+        assert_eq!(
+            check_validity_window(&WINDOW, 201, 1_500),
+            Err(ProxyError::SlotExpired.into())
+        );
+    }
+
+    #[test]
+    fn timestamp_bounds_are_inclusive() {
+        assert_eq!(check_validity_window(&WINDOW, 150, 1_000), Ok(()));
+        assert_eq!(check_validity_window(&WINDOW, 150, 2_000), Ok(()));
+        assert_eq!(
+            check_validity_window(&WINDOW, 150, 999),
+            Err(ProxyError::TimestampNotReached.into())
+        );
+        assert_eq!(
+            check_validity_window(&WINDOW, 150, 2_001),
+            Err(ProxyError::TimestampExpired.into())
+        );
+    }
+
+    #[test]
+    fn default_window_accepts_everything() {
+        let window = ValidityWindowArgs::default();
+        assert_eq!(check_validity_window(&window, 0, i64::MIN), Ok(()));
+        assert_eq!(check_validity_window(&window, u64::MAX, i64::MAX), Ok(()));
+    }
+}
@@ -0,0 +1,67 @@
+use amm_proxy_interface::{instruction::SwapExactInArgs, selector::token_swap};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction, pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::error::ProxyError;
+use crate::instructions::accounts::{to_account_metas, AccountRole, R, S, W};
+use crate::instructions::guard::{check_program_id, invoke_guarded, Balance};
+
+// 使用 SPL token-swap 指令布局的程序，新增分叉只需加入此列表
+#[cfg(not(feature = "devnet"))]
+const TOKEN_SWAP_PROGRAMS: [Pubkey; 5] = [
+    // SPL token-swap
+    pubkey!("SwapsVoB6hQ6Xhhv6ybsYcRJa3BSXMp1H1mdpBu2uZ9"),
+    // Orca v1
+    pubkey!("DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1"),
+    // Orca v2
+    pubkey!("9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP"),
+    // Step Finance
+    pubkey!("SSwpMgqNDsyV7mAgN9ady4bDVu5ySjmmXejXvy2vLt1"),
+    // Saros
+    pubkey!("SSwapUtytfBdBn1b9NUGG6foMVPtcWgpRU32HToDUZr"),
+];
+#[cfg(feature = "devnet")]
+const TOKEN_SWAP_PROGRAMS: [Pubkey; 1] = [pubkey!("SwapsVoB6hQ6Xhhv6ybsYcRJa3BSXMp1H1mdpBu2uZ9")];
+
+// 指令标签 (u8) + amount_in (u64) + minimum_amount_out (u64)
+const TOKEN_SWAP_DATA_LEN: usize = 17;
+
+const TOKEN_SWAP_ACCOUNTS_LEN: usize = 10;
+
+// swap, swap_authority, user_transfer_authority, user_source, pool_source, pool_destination,
+// user_destination, pool_mint, fee_account, token_program, [host_fee_account]
+const TOKEN_SWAP_ACCOUNTS: [AccountRole; TOKEN_SWAP_ACCOUNTS_LEN + 1] =
+    [R, R, S, W, W, W, W, W, W, R, W];
+
+const TOKEN_SWAP_USER_SOURCE_INDEX: usize = 3;
+const TOKEN_SWAP_USER_DESTINATION_INDEX: usize = 6;
+
+// 账户顺序：swap_program（须在允许列表中）+ token-swap Swap 的 10 个账户，可选再附 host_fee_account
+pub fn process_token_swap(accounts: &[AccountInfo], args: &SwapExactInArgs) -> ProgramResult {
+    let Some((swap_program, swap_accounts)) = accounts.split_first() else {
+        return Err(ProxyError::NotEnoughAccountKeys.into());
+    };
+
+    check_program_id(swap_program, &TOKEN_SWAP_PROGRAMS)?;
+
+    let metas = to_account_metas(swap_accounts, &TOKEN_SWAP_ACCOUNTS, TOKEN_SWAP_ACCOUNTS_LEN)?;
+
+    let mut data = Vec::with_capacity(TOKEN_SWAP_DATA_LEN);
+    data.push(token_swap::SWAP);
+    data.extend_from_slice(&args.amount_in.to_le_bytes());
+    data.extend_from_slice(&args.minimum_amount_out.to_le_bytes());
+
+    invoke_guarded(
+        &args.guard,
+        Balance::Token(&swap_accounts[TOKEN_SWAP_USER_SOURCE_INDEX]),
+        Balance::Token(&swap_accounts[TOKEN_SWAP_USER_DESTINATION_INDEX]),
+        &Instruction {
+            program_id: *swap_program.key,
+            accounts: metas,
+            data,
+        },
+        accounts,
+    )
+}
@@ -4,6 +4,7 @@ use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
 };
 
+pub mod error;
 pub mod instructions;
 pub mod processor;
 
@@ -1,40 +1,156 @@
+use amm_proxy_interface::{instruction::Args, selector};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
 
-use crate::instructions::ata::{process_create_associated_token_account, ATA_SELECTOR};
+use crate::error::ProxyError;
+use crate::instructions::ata::process_create_associated_token_account;
+use crate::instructions::launchlab::{
+    process_launchlab_buy_exact_in, process_launchlab_buy_exact_out,
+    process_launchlab_sell_exact_in, process_launchlab_sell_exact_out,
+};
+use crate::instructions::meteora_damm::{process_meteora_damm_swap, process_meteora_damm_v2_swap};
+use crate::instructions::meteora_dlmm::{
+    process_meteora_dlmm_swap, process_meteora_dlmm_swap_exact_out,
+};
+use crate::instructions::moonshot::{process_moonshot_buy, process_moonshot_sell};
+use crate::instructions::orca::{process_orca_swap, process_orca_swap_v2};
+use crate::instructions::orderbook::{process_openbook_v2_take, process_phoenix_take};
 use crate::instructions::pump::{
-    process_pump_amm_buy, process_pump_amm_sell, process_pump_buy, process_pump_sell,
-    PUMP_AMM_SELL_SELECTOR, PUMP_AMM_SELECTOR, PUMP_SELL_SELECTOR, PUMP_SELECTOR,
+    process_pump_amm_buy, process_pump_amm_buy_exact_quote_in, process_pump_amm_deposit,
+    process_pump_amm_sell, process_pump_amm_withdraw, process_pump_buy, process_pump_buy_exact_sol,
+    process_pump_launch, process_pump_sell, process_pump_trade,
+};
+use crate::instructions::raydium::{
+    process_raydium_buy, process_raydium_market_swap_base_in, process_raydium_market_swap_base_out,
+    process_raydium_sell, process_raydium_swap_base_in, process_raydium_swap_base_out,
 };
-use crate::instructions::raydium::{process_raydium_buy, process_raydium_sell, RAYDIUM_BUY_SELECTOR, RAYDIUM_SELL_SELECTOR};
-use crate::instructions::slot::{process_expired_slot, EXPIRED_SLOT_SELECTOR};
+use crate::instructions::raydium_clmm::process_raydium_clmm_swap;
+use crate::instructions::raydium_cpmm::{
+    process_raydium_cpmm_swap_base_input, process_raydium_cpmm_swap_base_output,
+};
+use crate::instructions::slot::{process_expired_slot, process_validity_window};
+use crate::instructions::token_swap::process_token_swap;
 
 type SelectorHandler = fn(&[AccountInfo], &[u8]) -> ProgramResult;
 
-const SELECTORS: [(&[u8; 8], SelectorHandler); 8] = [
-    (PUMP_SELECTOR, |accounts, rest| {
-        process_pump_buy(accounts, rest)
+fn unpack<T: Args>(data: &[u8]) -> Result<T, ProgramError> {
+    T::unpack(data).map_err(|e| ProxyError::from(e).into())
+}
+
+const SELECTORS: [(&[u8; 8], SelectorHandler); 37] = [
+    (&selector::PUMP_BUY, |accounts, rest| {
+        process_pump_buy(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_AMM_BUY, |accounts, rest| {
+        process_pump_amm_buy(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_SELL, |accounts, rest| {
+        process_pump_sell(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_AMM_SELL, |accounts, rest| {
+        process_pump_amm_sell(accounts, &unpack(rest)?)
+    }),
+    (&selector::CREATE_ATA, |accounts, rest| {
+        process_create_associated_token_account(accounts, &unpack(rest)?)
+    }),
+    (&selector::EXPIRED_SLOT, |_, rest| {
+        process_expired_slot(&unpack(rest)?)
+    }),
+    (&selector::VALIDITY_WINDOW, |_, rest| {
+        process_validity_window(&unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_BUY, |accounts, rest| {
+        process_raydium_buy(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_SELL, |accounts, rest| {
+        process_raydium_sell(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_SWAP_BASE_IN, |accounts, rest| {
+        process_raydium_swap_base_in(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_SWAP_BASE_OUT, |accounts, rest| {
+        process_raydium_swap_base_out(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_MARKET_SWAP_BASE_IN, |accounts, rest| {
+        process_raydium_market_swap_base_in(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_MARKET_SWAP_BASE_OUT, |accounts, rest| {
+        process_raydium_market_swap_base_out(accounts, &unpack(rest)?)
+    }),
+    (&selector::RAYDIUM_CPMM_SWAP_BASE_INPUT, |accounts, rest| {
+        process_raydium_cpmm_swap_base_input(accounts, &unpack(rest)?)
+    }),
+    (
+        &selector::RAYDIUM_CPMM_SWAP_BASE_OUTPUT,
+        |accounts, rest| process_raydium_cpmm_swap_base_output(accounts, &unpack(rest)?),
+    ),
+    (&selector::RAYDIUM_CLMM_SWAP, |accounts, rest| {
+        process_raydium_clmm_swap(accounts, &unpack(rest)?)
+    }),
+    (&selector::ORCA_SWAP, |accounts, rest| {
+        process_orca_swap(accounts, &unpack(rest)?)
     }),
-    (PUMP_AMM_SELECTOR, |accounts, rest: &[u8]| {
-        process_pump_amm_buy(accounts, rest)
+    (&selector::ORCA_SWAP_V2, |accounts, rest| {
+        process_orca_swap_v2(accounts, &unpack(rest)?)
     }),
-    (PUMP_SELL_SELECTOR, |accounts, rest| {
-        process_pump_sell(accounts, rest)
+    (&selector::METEORA_DLMM_SWAP, |accounts, rest| {
+        process_meteora_dlmm_swap(accounts, &unpack(rest)?)
     }),
-    (PUMP_AMM_SELL_SELECTOR, |accounts, rest| {
-        process_pump_amm_sell(accounts, rest)
+    (&selector::METEORA_DLMM_SWAP_EXACT_OUT, |accounts, rest| {
+        process_meteora_dlmm_swap_exact_out(accounts, &unpack(rest)?)
     }),
-    (ATA_SELECTOR, |accounts, rest| {
-        process_create_associated_token_account(accounts, rest)
+    (&selector::METEORA_DAMM_SWAP, |accounts, rest| {
+        process_meteora_damm_swap(accounts, &unpack(rest)?)
     }),
-    (EXPIRED_SLOT_SELECTOR, |_, rest| process_expired_slot(rest)),
-    (RAYDIUM_BUY_SELECTOR, |accounts, rest| {
-        process_raydium_buy(accounts, rest)
+    (&selector::METEORA_DAMM_V2_SWAP, |accounts, rest| {
+        process_meteora_damm_v2_swap(accounts, &unpack(rest)?)
     }),
-    (RAYDIUM_SELL_SELECTOR, |accounts, rest| {
-        process_raydium_sell(accounts, rest)
+    (&selector::LAUNCHLAB_BUY_EXACT_IN, |accounts, rest| {
+        process_launchlab_buy_exact_in(accounts, &unpack(rest)?)
+    }),
+    (&selector::LAUNCHLAB_BUY_EXACT_OUT, |accounts, rest| {
+        process_launchlab_buy_exact_out(accounts, &unpack(rest)?)
+    }),
+    (&selector::LAUNCHLAB_SELL_EXACT_IN, |accounts, rest| {
+        process_launchlab_sell_exact_in(accounts, &unpack(rest)?)
+    }),
+    (&selector::LAUNCHLAB_SELL_EXACT_OUT, |accounts, rest| {
+        process_launchlab_sell_exact_out(accounts, &unpack(rest)?)
+    }),
+    (&selector::OPENBOOK_V2_TAKE, |accounts, rest| {
+        process_openbook_v2_take(accounts, &unpack(rest)?)
+    }),
+    (&selector::PHOENIX_TAKE, |accounts, rest| {
+        process_phoenix_take(accounts, &unpack(rest)?)
+    }),
+    (&selector::MOONSHOT_BUY, |accounts, rest| {
+        process_moonshot_buy(accounts, &unpack(rest)?)
+    }),
+    (&selector::MOONSHOT_SELL, |accounts, rest| {
+        process_moonshot_sell(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_LAUNCH, |accounts, rest| {
+        process_pump_launch(accounts, &unpack(rest)?)
+    }),
+    (&selector::TOKEN_SWAP, |accounts, rest| {
+        process_token_swap(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_BUY_EXACT_SOL, |accounts, rest| {
+        process_pump_buy_exact_sol(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_TRADE, |accounts, rest| {
+        process_pump_trade(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_AMM_BUY_EXACT_QUOTE_IN, |accounts, rest| {
+        process_pump_amm_buy_exact_quote_in(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_AMM_DEPOSIT, |accounts, rest| {
+        process_pump_amm_deposit(accounts, &unpack(rest)?)
+    }),
+    (&selector::PUMP_AMM_WITHDRAW, |accounts, rest| {
+        process_pump_amm_withdraw(accounts, &unpack(rest)?)
     }),
 ];
 
@@ -43,6 +159,9 @@ pub fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    if instruction_data.len() < 8 {
+        return Err(ProxyError::InstructionDataTooShort.into());
+    }
     let (method, rest) = instruction_data.split_at(8);
 
     for (selector, handler) in SELECTORS.iter() {
@@ -51,5 +170,21 @@ pub fn process_instruction(
         }
     }
 
-    Err(ProgramError::InvalidInstructionData)
+    Err(ProxyError::UnknownSelector.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_selector_has_a_handler() {
+        assert_eq!(SELECTORS.len(), selector::ALL.len());
+        for selector in selector::ALL.iter() {
+            assert!(
+                SELECTORS.iter().any(|(s, _)| *s == selector),
+                "{selector:?}"
+            );
+        }
+    }
 }
@@ -1,5 +1,9 @@
 use std::{env, fmt::Error};
 
+use amm_proxy_interface::{
+    instruction::{CreateAtaArgs, PumpBuyArgs, PumpSellArgs, RaydiumSwapBaseInArgs, SwapGuard},
+    ProxyInstruction,
+};
 use dotenvy::dotenv;
 use rand::{distr::Alphanumeric, Rng};
 use sha2::{Digest, Sha256};
@@ -9,7 +13,6 @@ use solana_sdk::{
     commitment_config::{CommitmentConfig, CommitmentLevel},
     instruction::{AccountMeta, Instruction},
     pubkey::{self, Pubkey},
-    rent::sysvar,
     signer::Signer,
     system_instruction::create_account_with_seed,
     system_program,
@@ -37,6 +40,7 @@ pub const FEE_RECIPIENT: Pubkey =
 const EVENT_AUTHORITY: Pubkey = solana_sdk::pubkey!("Ce6TQqeHC9p8KetsN6JsjHK7UTZk7nasjjnr7XxXp9F1");
 pub const PUMP_PROGRAM_ID: Pubkey =
     solana_sdk::pubkey!("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
+const PUMP_FEE_PROGRAM: Pubkey = solana_sdk::pubkey!("pfeeUxB6jkeY1Hxd7CsFCAjcbHA9rWtchMGdZ6VojVZ");
 
 const PROXY_PROGRAM: Pubkey = solana_sdk::pubkey!("AmXoSVCLjsfKrwCUqvkMFXYcDzZ4FeoMYs7SAhGyfMGy");
 
@@ -45,16 +49,26 @@ const RAYDIUM_PROGRAM_ID: Pubkey =
 const AMM_AUTHORITY: Pubkey = solana_sdk::pubkey!("5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1");
 const WSOL: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
 
-pub const PUMP_SELECTOR: &[u8; 8] = &[82, 225, 119, 231, 78, 29, 45, 70];       // 内盘买入鉴别器
-pub const PUMP_AMM_SELECTOR: &[u8; 8] = &[129, 59, 179, 195, 110, 135, 61, 2];  // 外盘买入鉴别器
-pub const PUMP_SELL_SELECTOR: &[u8; 8] = &[83, 225, 119, 231, 78, 29, 45, 70];  // 内盘卖出鉴别器
-pub const PUMP_AMM_SELL_SELECTOR: &[u8; 8] = &[130, 59, 179, 195, 110, 135, 61, 2]; // 外盘卖出鉴别器
-
-pub const ATA_SELECTOR: &[u8; 8] = &[22, 51, 53, 97, 247, 184, 54, 78];
-pub const RAYDIUM_BUY_SELECTOR: &[u8; 8] = &[182, 77, 232, 39, 117, 138, 183, 72];
-pub const RAYDIUM_SELL_SELECTOR: &[u8; 8] = &[183, 77, 232, 39, 117, 138, 183, 72];
-
 const BONDING_CURVE_SEED: &[u8] = b"bonding-curve";
+// BondingCurve 账户中 creator 的偏移
+const BONDING_CURVE_CREATOR_OFFSET: usize = 49;
+
+// 新版布局需要的 creator_vault，按联合曲线上记录的 creator 推导
+async fn pump_creator_vault(rpc_client: &RpcClient, bonding_curve: &Pubkey) -> Pubkey {
+    let data = rpc_client.get_account_data(bonding_curve).await.unwrap();
+    let creator =
+        Pubkey::try_from(&data[BONDING_CURVE_CREATOR_OFFSET..BONDING_CURVE_CREATOR_OFFSET + 32])
+            .unwrap();
+    Pubkey::find_program_address(&[b"creator-vault", creator.as_ref()], &PUMP_PROGRAM_ID).0
+}
+
+fn pump_fee_config() -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fee_config", PUMP_PROGRAM_ID.as_ref()],
+        &PUMP_FEE_PROGRAM,
+    )
+    .0
+}
 
 // 生成判别符
 fn generate_discriminant() -> [u8; 8] {
@@ -78,11 +92,16 @@ async fn pump_buy() -> Result<(), Error> {
         RpcClient::new_with_commitment("".to_string(), CommitmentConfig::confirmed());
 
     let token_amount = 351100_u64;
-    let max_sol_cost = 11000000_u64;
-    let mut data = Vec::with_capacity(24);
-    data.extend_from_slice(PUMP_SELECTOR);
-    data.extend_from_slice(&token_amount.to_le_bytes());
-    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    let data = ProxyInstruction::PumpBuy(PumpBuyArgs {
+        token_amount,
+        max_sol_cost: 11000000,
+        // 由代理合约校验实际到账的代币数量
+        guard: SwapGuard {
+            min_out: token_amount,
+            ..SwapGuard::NONE
+        },
+    })
+    .pack();
 
     let signer = solana_sdk::signature::Keypair::from_base58_string(&private_key);
 
@@ -96,6 +115,15 @@ async fn pump_buy() -> Result<(), Error> {
     let associated_bonding_curve =
         get_associated_token_address(&bonding_curve_address.0, &token_mint);
 
+    let creator_vault = pump_creator_vault(&rpc_client, &bonding_curve_address.0).await;
+    let global_volume_accumulator =
+        Pubkey::find_program_address(&[b"global_volume_accumulator"], &PUMP_PROGRAM_ID).0;
+    let user_volume_accumulator = Pubkey::find_program_address(
+        &[b"user_volume_accumulator", signer.pubkey().as_ref()],
+        &PUMP_PROGRAM_ID,
+    )
+    .0;
+
     let instruction = Instruction::new_with_bytes(
         PROXY_PROGRAM,
         &data,
@@ -109,9 +137,13 @@ async fn pump_buy() -> Result<(), Error> {
             AccountMeta::new(signer.pubkey(), true),
             AccountMeta::new_readonly(system_program::id(), false),
             AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(sysvar::id(), false),
+            AccountMeta::new(creator_vault, false),
             AccountMeta::new_readonly(EVENT_AUTHORITY, false),
             AccountMeta::new_readonly(PUMP_PROGRAM_ID, false),
+            AccountMeta::new(global_volume_accumulator, false),
+            AccountMeta::new(user_volume_accumulator, false),
+            AccountMeta::new_readonly(pump_fee_config(), false),
+            AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
         ],
     );
     let blockhash = rpc_client
@@ -121,11 +153,7 @@ async fn pump_buy() -> Result<(), Error> {
         .await
         .unwrap();
 
-    let mut ata_data = Vec::with_capacity(9);
-
-    ata_data.extend_from_slice(ATA_SELECTOR);
-
-    ata_data.extend_from_slice(&[0]);
+    let ata_data = ProxyInstruction::CreateAta(CreateAtaArgs { idempotent: false }).pack();
 
     let ata_instruction = Instruction::new_with_bytes(
         PROXY_PROGRAM,
@@ -177,12 +205,12 @@ async fn raydium_buy() -> Result<(), Error> {
 
     let token_amount = 351100_u64;
     let max_sol_cost = 11000000_u64;
-    let mut data = Vec::with_capacity(25);
-
-    data.extend_from_slice(RAYDIUM_BUY_SELECTOR);
-    data.extend_from_slice(&[9]);
-    data.extend_from_slice(&token_amount.to_le_bytes());
-    data.extend_from_slice(&max_sol_cost.to_le_bytes());
+    let data = ProxyInstruction::RaydiumSwapBaseIn(RaydiumSwapBaseInArgs {
+        amount_in: token_amount,
+        minimum_amount_out: max_sol_cost,
+        guard: SwapGuard::NONE,
+    })
+    .pack();
 
     let output_mint: Pubkey = solana_sdk::pubkey!("DYUjm68jHoQFHMHzuRqomrhRcog9mc4TNrCWHpufpump");
 
@@ -273,12 +301,12 @@ async fn pump_sell() -> Result<(), Error> {
     let rpc_client =
         RpcClient::new_with_commitment("".to_string(), CommitmentConfig::confirmed());
 
-    let token_amount = 351100_u64;
-    let min_sol_receive = 10000000_u64;
-    let mut data = Vec::with_capacity(24);
-    data.extend_from_slice(PUMP_SELL_SELECTOR);
-    data.extend_from_slice(&token_amount.to_le_bytes());
-    data.extend_from_slice(&min_sol_receive.to_le_bytes());
+    let data = ProxyInstruction::PumpSell(PumpSellArgs {
+        token_amount: 351100,
+        min_sol_output: 10000000,
+        guard: SwapGuard::NONE,
+    })
+    .pack();
 
     let signer = solana_sdk::signature::Keypair::from_base58_string(&private_key);
 
@@ -292,6 +320,8 @@ async fn pump_sell() -> Result<(), Error> {
     let associated_bonding_curve =
         get_associated_token_address(&bonding_curve_address.0, &token_mint);
 
+    let creator_vault = pump_creator_vault(&rpc_client, &bonding_curve_address.0).await;
+
     let instruction = Instruction::new_with_bytes(
         PROXY_PROGRAM,
         &data,
@@ -304,10 +334,12 @@ async fn pump_sell() -> Result<(), Error> {
             AccountMeta::new(associated_user, false),
             AccountMeta::new(signer.pubkey(), true),
             AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(creator_vault, false),
             AccountMeta::new_readonly(spl_token::id(), false),
-            AccountMeta::new_readonly(sysvar::id(), false),
             AccountMeta::new_readonly(EVENT_AUTHORITY, false),
             AccountMeta::new_readonly(PUMP_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pump_fee_config(), false),
+            AccountMeta::new_readonly(PUMP_FEE_PROGRAM, false),
         ],
     );
 
@@ -351,14 +383,12 @@ async fn raydium_sell() -> Result<(), Error> {
         RpcClient::new_with_commitment("".to_string(), CommitmentConfig::confirmed());
     let signer = solana_sdk::signature::Keypair::from_base58_string(&private_key);
 
-    let token_amount = 351100_u64;
-    let min_sol_receive = 10000000_u64;
-    let mut data = Vec::with_capacity(25);
-
-    data.extend_from_slice(RAYDIUM_SELL_SELECTOR);
-    data.extend_from_slice(&[9]);
-    data.extend_from_slice(&token_amount.to_le_bytes());
-    data.extend_from_slice(&min_sol_receive.to_le_bytes());
+    let data = ProxyInstruction::RaydiumSwapBaseIn(RaydiumSwapBaseInArgs {
+        amount_in: 351100,
+        minimum_amount_out: 10000000,
+        guard: SwapGuard::NONE,
+    })
+    .pack();
 
     let input_mint: Pubkey = solana_sdk::pubkey!("DYUjm68jHoQFHMHzuRqomrhRcog9mc4TNrCWHpufpump");
 